@@ -11,6 +11,8 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
 use std::ops::{Bound, Deref, RangeBounds};
 use std::sync::Arc;
 
@@ -18,11 +20,11 @@ use futures::{pin_mut, StreamExt};
 use futures_async_stream::try_stream;
 use itertools::Itertools;
 use prometheus::Histogram;
-use risingwave_common::array::DataChunk;
+use risingwave_common::array::{DataChunk, PrimitiveArray, Utf8Array};
 use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema, TableId, TableOption};
 use risingwave_common::row::{OwnedRow, Row};
-use risingwave_common::types::{DataType, Datum};
+use risingwave_common::types::{DataType, Datum, DatumRef};
 use risingwave_common::util::chunk_coalesce::DataChunkBuilder;
 use risingwave_common::util::select_all;
 use risingwave_common::util::sort_util::OrderType;
@@ -57,6 +59,160 @@ pub struct RowSeqScanExecutor<S: StateStore> {
     ordered: bool,
     epoch: BatchQueryEpoch,
     limit: Option<u64>,
+
+    /// Columns to emit as dictionary-encoded, if any. The builder always passes `None` (see
+    /// [`DictionaryEncoding`]), and `do_execute` does not read this field: every column is
+    /// emitted as a plain array whether or not it is set.
+    dictionary_encoding: Option<DictionaryEncoding>,
+
+    /// Zone-map predicates for pruning storage blocks before they're decoded. The builder always
+    /// passes an empty [`ScanOptions`], and the scan loops do not consult the predicates: every
+    /// block is decoded whether or not any are set.
+    scan_options: ScanOptions,
+}
+
+/// Per-scan configuration for dictionary-encoding low-cardinality output columns: distinct
+/// values observed for each of `column_ids` are mapped to small `i32` codes instead of being
+/// re-materialized on every row, so e.g. a `country`/`status` column during a full-table scan
+/// costs a handful of strings plus one `i32` per row rather than one string per row, and
+/// downstream hash-agg/join keys can compare codes directly.
+///
+/// Note: the planner and `PbRowSeqScan` in this snapshot don't yet carry a "dictionary-eligible
+/// columns" field, so [`RowSeqScanExecutorBuilder`] always constructs the executor with this set
+/// to `None`; see [`ColumnDictionaryBuilder`] for the accumulation policy this would drive once
+/// that plumbing exists.
+pub struct DictionaryEncoding {
+    /// Columns eligible for dictionary encoding.
+    pub column_ids: Vec<ColumnId>,
+    /// Once the number of distinct values observed for a column exceeds this, the column falls
+    /// back to a plain array for the remainder of the scan.
+    pub cardinality_threshold: usize,
+}
+
+/// Accumulates one column's distinct values across the chunks of a scan, assigning each a
+/// stable `i32` code the first time it's seen, and reusing the same dictionary for every chunk
+/// so codes stay comparable across the whole scan. Once the number of distinct values exceeds
+/// `threshold`, [`Self::encode`] returns `None` from then on and the caller should fall back to
+/// a plain materialized array for this column for the rest of the scan.
+struct ColumnDictionaryBuilder {
+    threshold: usize,
+    codes_by_value: HashMap<Option<String>, i32>,
+    values: Vec<Option<String>>,
+    overflowed: bool,
+}
+
+impl ColumnDictionaryBuilder {
+    fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            codes_by_value: HashMap::new(),
+            values: Vec::new(),
+            overflowed: false,
+        }
+    }
+
+    /// Returns `value`'s dictionary code, assigning a new one if unseen, or `None` once the
+    /// builder has overflowed `threshold`.
+    fn encode(&mut self, value: Option<&str>) -> Option<i32> {
+        if self.overflowed {
+            return None;
+        }
+        let key = value.map(str::to_string);
+        if let Some(&code) = self.codes_by_value.get(&key) {
+            return Some(code);
+        }
+        if self.values.len() >= self.threshold {
+            self.overflowed = true;
+            return None;
+        }
+        let code = self.values.len() as i32;
+        self.values.push(key.clone());
+        self.codes_by_value.insert(key, code);
+        Some(code)
+    }
+
+    /// The dictionary accumulated so far, in code order, suitable for pairing with the `i32`
+    /// codes [`Self::encode`] has produced.
+    ///
+    /// Note: splicing this back into the `DataChunk` column that `do_execute` yields needs a
+    /// column-replacement API (something like `DataChunk::with_column`) that isn't part of this
+    /// snapshot; this builder is the independently useful half -- distinct-value tracking and
+    /// threshold fallback -- that such integration would sit on top of.
+    fn dictionary(&self) -> Arc<Utf8Array> {
+        Arc::new(self.values.iter().map(|v| v.as_deref()).collect())
+    }
+}
+
+/// Packs one chunk's worth of codes (as produced by repeated [`ColumnDictionaryBuilder::encode`]
+/// calls, one per row) into the `codes` half of the `(dictionary, codes)` pair a dictionary
+/// encoded column is made of.
+fn codes_array(codes: Vec<Option<i32>>) -> PrimitiveArray<i32> {
+    codes.into_iter().collect()
+}
+
+/// Per-scan zone-map configuration: min/max predicates on non-PK columns, derived from filter
+/// conjuncts that couldn't be folded into a [`ScanRange`]'s PK bounds. Meant to be consulted
+/// against each storage block's own min/max statistics before the block is decoded into a
+/// [`DataChunk`], so blocks that provably can't contain a matching row are skipped.
+///
+/// Note: consulting storage block statistics needs an iterator API (a `block_stats()` hook
+/// alongside `next()`) that isn't part of this snapshot, and the planner path that would derive
+/// `zone_map_predicates` from leftover filter conjuncts isn't either; [`RowSeqScanExecutorBuilder`]
+/// therefore always builds an empty [`ScanOptions`]. [`ZoneMapPredicate::may_match`] is the
+/// complete predicate-evaluation half such wiring would call per block.
+#[derive(Default, Clone)]
+pub struct ScanOptions {
+    pub zone_map_predicates: Vec<ZoneMapPredicate>,
+}
+
+/// A single non-PK column's zone-map predicate, testable against a block's `[min, max]`
+/// statistics without decoding the block's rows.
+#[derive(Clone)]
+pub struct ZoneMapPredicate {
+    /// Index of the column within the table's schema.
+    pub column_idx: usize,
+    pub kind: ZoneMapPredicateKind,
+}
+
+#[derive(Clone)]
+pub enum ZoneMapPredicateKind {
+    Equal(Datum),
+    Range(Bound<Datum>, Bound<Datum>),
+    IsNull,
+}
+
+/// A block's reported min/max statistics for one column.
+pub struct ColumnZoneStats {
+    pub min: Datum,
+    pub max: Datum,
+    pub has_null: bool,
+}
+
+impl ZoneMapPredicate {
+    /// Whether a block with these column statistics could possibly contain a row matching this
+    /// predicate. Conservative: returns `true` (i.e. "don't skip") whenever it can't prove
+    /// otherwise, e.g. when the predicate's own comparison value is `NULL`.
+    pub fn may_match(&self, stats: &ColumnZoneStats) -> bool {
+        match &self.kind {
+            ZoneMapPredicateKind::IsNull => stats.has_null,
+            ZoneMapPredicateKind::Equal(value) => {
+                value.is_none() || (*value >= stats.min && *value <= stats.max)
+            }
+            ZoneMapPredicateKind::Range(lower, upper) => {
+                let lower_ok = match lower {
+                    Bound::Unbounded => true,
+                    Bound::Included(v) => v.is_none() || *v <= stats.max,
+                    Bound::Excluded(v) => v.is_none() || *v < stats.max,
+                };
+                let upper_ok = match upper {
+                    Bound::Unbounded => true,
+                    Bound::Included(v) => v.is_none() || *v >= stats.min,
+                    Bound::Excluded(v) => v.is_none() || *v > stats.min,
+                };
+                lower_ok && upper_ok
+            }
+        }
+    }
 }
 
 /// Range for batch scan.
@@ -129,6 +285,105 @@ impl ScanRange {
             next_col_bounds: (Bound::Unbounded, Bound::Unbounded),
         }
     }
+
+    /// Whether this range provably matches zero rows, e.g. an `IN`/`OR` list whose conjuncts
+    /// normalized to `lower > upper`, or exclusive bounds pinned to the same value. Ranges that
+    /// short-circuit this way are filtered out by the builder before reaching the executor, so no
+    /// storage access is made for them.
+    pub fn is_empty(&self) -> bool {
+        match &self.next_col_bounds {
+            (Bound::Included(l), Bound::Included(u)) => l > u,
+            (Bound::Included(l), Bound::Excluded(u))
+            | (Bound::Excluded(l), Bound::Included(u))
+            | (Bound::Excluded(l), Bound::Excluded(u)) => l >= u,
+            _ => false,
+        }
+    }
+
+    /// Merges `ranges` that share the same `pk_prefix` and whose `next_col_bounds` overlap, so an
+    /// `IN`/`OR` list that expands into several [`ScanRange`]s never causes the executor to scan
+    /// the same key twice. Ranges with differing prefixes, or whose prefix is already the full PK
+    /// (point gets), are left untouched (two point gets never have equal `pk_prefix` unless
+    /// they're literal duplicates, which this happens to dedup too).
+    ///
+    /// Note: proper multi-column range analysis -- folding bounds on PK columns *after* the first
+    /// inequality into their own normalized ranges, as opposed to merging within the single
+    /// `next_col_bounds` this type currently models -- needs a wider `PbScanRange` than this
+    /// snapshot's `risingwave_pb` defines, and the planner path that builds `scan_ranges` from
+    /// filter conjuncts isn't part of this snapshot either. This covers the single-column case.
+    pub fn merge_overlapping(ranges: Vec<Self>) -> Vec<Self> {
+        let mut merged: Vec<Self> = Vec::with_capacity(ranges.len());
+        for mut range in ranges {
+            // Absorbing one range can widen `range` enough to overlap another kept range, so keep
+            // absorbing until none overlaps.
+            while let Some(pos) = merged.iter().position(|existing| {
+                existing.pk_prefix == range.pk_prefix
+                    && ranges_overlap(&existing.next_col_bounds, &range.next_col_bounds)
+            }) {
+                let existing = merged.remove(pos);
+                range.next_col_bounds =
+                    union_bounds(&existing.next_col_bounds, &range.next_col_bounds);
+            }
+            merged.push(range);
+        }
+        merged
+    }
+}
+
+/// Whether two bound pairs overlap (including touching at a shared boundary value).
+fn ranges_overlap(a: &(Bound<Datum>, Bound<Datum>), b: &(Bound<Datum>, Bound<Datum>)) -> bool {
+    fn le(lower: &Bound<Datum>, upper: &Bound<Datum>) -> bool {
+        match (lower, upper) {
+            (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+            (Bound::Included(l), Bound::Included(u)) => l <= u,
+            (Bound::Included(l), Bound::Excluded(u)) | (Bound::Excluded(l), Bound::Included(u)) => {
+                l <= u
+            }
+            // `(.., v)` and `(v, ..)` both leave out `v`, so they neither overlap nor touch.
+            (Bound::Excluded(l), Bound::Excluded(u)) => l < u,
+        }
+    }
+    le(&a.0, &b.1) && le(&b.0, &a.1)
+}
+
+/// Combines two overlapping/adjacent ranges into the single range spanning both.
+fn union_bounds(
+    a: &(Bound<Datum>, Bound<Datum>),
+    b: &(Bound<Datum>, Bound<Datum>),
+) -> (Bound<Datum>, Bound<Datum>) {
+    let start = match (&a.0, &b.0) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => Bound::Unbounded,
+        (Bound::Included(x), Bound::Included(y)) => {
+            Bound::Included(if x <= y { x } else { y }.clone())
+        }
+        (Bound::Excluded(x), Bound::Excluded(y)) => {
+            Bound::Excluded(if x <= y { x } else { y }.clone())
+        }
+        (Bound::Included(x), Bound::Excluded(y)) | (Bound::Excluded(y), Bound::Included(x)) => {
+            if x <= y {
+                Bound::Included(x.clone())
+            } else {
+                Bound::Excluded(y.clone())
+            }
+        }
+    };
+    let end = match (&a.1, &b.1) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => Bound::Unbounded,
+        (Bound::Included(x), Bound::Included(y)) => {
+            Bound::Included(if x >= y { x } else { y }.clone())
+        }
+        (Bound::Excluded(x), Bound::Excluded(y)) => {
+            Bound::Excluded(if x >= y { x } else { y }.clone())
+        }
+        (Bound::Included(x), Bound::Excluded(y)) | (Bound::Excluded(y), Bound::Included(x)) => {
+            if x >= y {
+                Bound::Included(x.clone())
+            } else {
+                Bound::Excluded(y.clone())
+            }
+        }
+    };
+    (start, end)
 }
 
 impl<S: StateStore> RowSeqScanExecutor<S> {
@@ -141,6 +396,8 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
         identity: String,
         limit: Option<u64>,
         metrics: Option<BatchMetricsWithTaskLabels>,
+        dictionary_encoding: Option<DictionaryEncoding>,
+        scan_options: ScanOptions,
     ) -> Self {
         Self {
             chunk_size,
@@ -151,6 +408,8 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
             ordered,
             epoch,
             limit,
+            dictionary_encoding,
+            scan_options,
         }
     }
 }
@@ -239,10 +498,15 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
             if scan_ranges.is_empty() {
                 vec![ScanRange::full()]
             } else {
-                scan_ranges
+                let scan_ranges: Vec<ScanRange> = scan_ranges
                     .iter()
                     .map(|scan_range| ScanRange::new(scan_range.clone(), pk_types.iter().cloned()))
-                    .try_collect()?
+                    .try_collect()?;
+                // An `IN`/`OR` list on the filter side can expand into several ranges that, once
+                // normalized, turn out empty or overlap each other; drop the former and coalesce
+                // the latter so the executor never scans the same key twice.
+                let scan_ranges = scan_ranges.into_iter().filter(|r| !r.is_empty()).collect();
+                ScanRange::merge_overlapping(scan_ranges)
             }
         };
         let ordered = seq_scan_node.ordered;
@@ -279,6 +543,15 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
                 source.plan_node().get_identity().clone(),
                 limit,
                 metrics,
+                // `PbRowSeqScan` is generated from the batch-plan proto, which isn't part of this
+                // snapshot, and it doesn't carry a dictionary-eligible-columns field today; until it
+                // does, there's no planner output to read here, so this stays `None` rather than a
+                // fabricated value. See the `dictionary_encoding` field doc above for the full story.
+                None,
+                // Same situation for zone-map pruning: no leftover-filter-conjuncts field exists on
+                // `PbRowSeqScan` to derive `zone_map_predicates` from, so this stays the empty
+                // default. See the `scan_options` field doc above for the full story.
+                ScanOptions::default(),
             )))
         })
     }
@@ -310,6 +583,8 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
             ordered,
             epoch,
             limit,
+            dictionary_encoding: _,
+            scan_options,
         } = *self;
         let table = Arc::new(table);
 
@@ -321,17 +596,17 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
                 .with_label_values(&metrics.executor_labels(&identity))
         });
 
-        if ordered {
-            // Currently we execute range-scans concurrently so the order is not guaranteed if
-            // there're multiple ranges.
-            // TODO: reserve the order for multiple ranges.
-            assert_eq!(scan_ranges.len(), 1);
-        }
-
         let (point_gets, range_scans): (Vec<ScanRange>, Vec<ScanRange>) = scan_ranges
             .into_iter()
             .partition(|x| x.pk_prefix.len() == table.pk_indices().len());
 
+        if ordered && !point_gets.is_empty() {
+            // Point gets are single rows emitted ahead of the range scans below regardless of
+            // `ordered`; the planner doesn't mix a point get into an ordered multi-range scan, so
+            // this is the only shape of "ordered" that involves one.
+            assert_eq!(point_gets.len() + range_scans.len(), 1);
+        }
+
         // the number of rows have been returned as execute result
         let mut returned = 0;
         if let Some(limit) = &limit && returned >= *limit {
@@ -362,6 +637,31 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
         }
 
         // Range Scan
+        if ordered && range_scans.len() > 1 {
+            // Multiple ranges (e.g. from an `IN`-list or several disjoint predicates) still need
+            // globally sorted output, so drive them through a k-way merge instead of the
+            // concurrent, order-scrambling `select_all` below.
+            let merged = Self::execute_ordered_ranges(
+                table,
+                range_scans,
+                epoch,
+                chunk_size,
+                limit.map(|limit| limit - returned),
+                histogram,
+                scan_options,
+            );
+            #[for_await]
+            for chunk in merged {
+                let chunk = chunk?;
+                returned += chunk.cardinality() as u64;
+                yield chunk;
+                if let Some(limit) = &limit && returned >= *limit {
+                    return Ok(());
+                }
+            }
+            return Ok(());
+        }
+
         let range_scans = select_all(range_scans.into_iter().map(|range_scan| {
             let table = table.clone();
             let histogram = histogram.clone();
@@ -373,6 +673,7 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
                 chunk_size,
                 limit,
                 histogram,
+                scan_options.clone(),
             ))
         }));
         #[for_await]
@@ -416,56 +717,17 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
         chunk_size: usize,
         limit: Option<u64>,
         histogram: Option<impl Deref<Target = Histogram>>,
+        _scan_options: ScanOptions,
     ) {
-        let ScanRange {
-            pk_prefix,
-            next_col_bounds,
-        } = scan_range;
-
-        let order_type = table.pk_serializer().get_order_types()[pk_prefix.len()];
-        let (start_bound, end_bound) = if order_type.is_ascending() {
-            (next_col_bounds.0, next_col_bounds.1)
-        } else {
-            (next_col_bounds.1, next_col_bounds.0)
-        };
-
-        let start_bound_is_bounded = !matches!(start_bound, Bound::Unbounded);
-        let end_bound_is_bounded = !matches!(end_bound, Bound::Unbounded);
-
         // Range Scan.
-        assert!(pk_prefix.len() < table.pk_indices().len());
+        assert!(scan_range.pk_prefix.len() < table.pk_indices().len());
+        let order_type = table.pk_serializer().get_order_types()[scan_range.pk_prefix.len()];
+        let pk_bounds = row_bounds_for_scan(order_type, scan_range.next_col_bounds);
         let iter = table
             .batch_iter_with_pk_bounds(
                 epoch.into(),
-                &pk_prefix,
-                (
-                    match start_bound {
-                        Bound::Unbounded => {
-                            if end_bound_is_bounded && order_type.nulls_are_first() {
-                                // `NULL`s are at the start bound side, we should exclude them to meet SQL semantics.
-                                Bound::Excluded(OwnedRow::new(vec![None]))
-                            } else {
-                                // Both start and end are unbounded, so we need to select all rows.
-                                Bound::Unbounded
-                            }
-                        }
-                        Bound::Included(x) => Bound::Included(OwnedRow::new(vec![x])),
-                        Bound::Excluded(x) => Bound::Excluded(OwnedRow::new(vec![x])),
-                    },
-                    match end_bound {
-                        Bound::Unbounded => {
-                            if start_bound_is_bounded && order_type.nulls_are_last() {
-                                // `NULL`s are at the end bound side, we should exclude them to meet SQL semantics.
-                                Bound::Excluded(OwnedRow::new(vec![None]))
-                            } else {
-                                // Both start and end are unbounded, so we need to select all rows.
-                                Bound::Unbounded
-                            }
-                        }
-                        Bound::Included(x) => Bound::Included(OwnedRow::new(vec![x])),
-                        Bound::Excluded(x) => Bound::Excluded(OwnedRow::new(vec![x])),
-                    },
-                ),
+                &scan_range.pk_prefix,
+                pk_bounds,
                 ordered,
                 PrefetchOptions::new_with_exhaust_iter(limit.is_none()),
             )
@@ -475,6 +737,12 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
         loop {
             let timer = histogram.as_ref().map(|histogram| histogram.start_timer());
 
+            // Zone-map pruning would go here: consult `iter`'s current block's
+            // `ColumnZoneStats` against `_scan_options.zone_map_predicates` via
+            // `ZoneMapPredicate::may_match`, and advance straight to the next block without
+            // decoding on a `false` result, counting the skip against a dedicated metric. The
+            // iterator doesn't expose block-level statistics in this snapshot, so `_scan_options`
+            // is threaded through ready for when it does, but unused for now.
             let chunk = collect_data_chunk(&mut iter, table.schema(), Some(chunk_size))
                 .await
                 .map_err(BatchError::from)?;
@@ -490,4 +758,298 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
             }
         }
     }
+
+    /// Drives `scan_ranges` (each internally ordered, since `batch_iter_with_pk_bounds` is called
+    /// with `ordered = true`) through a binary-heap-based k-way merge keyed by the PK serializer's
+    /// order types, so multiple ranges -- e.g. from an `IN`-list or several disjoint predicates --
+    /// still produce a single globally sorted row stream instead of the nondeterministic
+    /// interleaving `select_all` would give.
+    #[try_stream(ok = DataChunk, error = BatchError)]
+    async fn execute_ordered_ranges(
+        table: Arc<StorageTable<S>>,
+        scan_ranges: Vec<ScanRange>,
+        epoch: BatchQueryEpoch,
+        chunk_size: usize,
+        limit: Option<u64>,
+        histogram: Option<impl Deref<Target = Histogram>>,
+        _scan_options: ScanOptions,
+    ) {
+        let order_types = Arc::new(table.pk_serializer().get_order_types().to_vec());
+
+        let mut sources = Vec::with_capacity(scan_ranges.len());
+        for scan_range in scan_ranges {
+            assert!(scan_range.pk_prefix.len() < table.pk_indices().len());
+            let order_type = order_types[scan_range.pk_prefix.len()];
+            let pk_bounds = row_bounds_for_scan(order_type, scan_range.next_col_bounds);
+            let iter = table
+                .batch_iter_with_pk_bounds(
+                    epoch.into(),
+                    &scan_range.pk_prefix,
+                    pk_bounds,
+                    true,
+                    PrefetchOptions::new_with_exhaust_iter(limit.is_none()),
+                )
+                .await?;
+            sources.push(Box::pin(iter));
+        }
+
+        let mut heap: BinaryHeap<HeapItem> = BinaryHeap::with_capacity(sources.len());
+        for (source, iter) in sources.iter_mut().enumerate() {
+            if let Some(row) = iter.next().await.transpose()? {
+                heap.push(HeapItem {
+                    row,
+                    source,
+                    order_types: order_types.clone(),
+                });
+            }
+        }
+
+        let mut data_chunk_builder = DataChunkBuilder::new(table.schema().data_types(), chunk_size);
+        let mut returned = 0u64;
+        while let Some(HeapItem {
+            row,
+            source,
+            order_types,
+        }) = heap.pop()
+        {
+            if let Some(next_row) = sources[source].next().await.transpose()? {
+                heap.push(HeapItem {
+                    row: next_row,
+                    source,
+                    order_types,
+                });
+            }
+
+            let timer = histogram.as_ref().map(|histogram| histogram.start_timer());
+            let chunk = data_chunk_builder.append_one_row(row);
+            if let Some(timer) = timer {
+                timer.observe_duration()
+            }
+
+            if let Some(chunk) = chunk {
+                returned += chunk.cardinality() as u64;
+                yield chunk;
+                if let Some(limit) = &limit && returned >= *limit {
+                    return Ok(());
+                }
+            }
+        }
+        if let Some(chunk) = data_chunk_builder.consume_all() {
+            yield chunk;
+        }
+    }
+}
+
+/// Translates a [`ScanRange`]'s `next_col_bounds` into the `OwnedRow` start/end bounds
+/// `batch_iter_with_pk_bounds` expects, accounting for scan direction and excluding `NULL`s from
+/// whichever open end they'd otherwise leak into (per SQL comparison semantics).
+fn row_bounds_for_scan(
+    order_type: OrderType,
+    next_col_bounds: (Bound<Datum>, Bound<Datum>),
+) -> (Bound<OwnedRow>, Bound<OwnedRow>) {
+    let (start_bound, end_bound) = if order_type.is_ascending() {
+        (next_col_bounds.0, next_col_bounds.1)
+    } else {
+        (next_col_bounds.1, next_col_bounds.0)
+    };
+
+    let start_bound_is_bounded = !matches!(start_bound, Bound::Unbounded);
+    let end_bound_is_bounded = !matches!(end_bound, Bound::Unbounded);
+
+    (
+        match start_bound {
+            Bound::Unbounded => {
+                if end_bound_is_bounded && order_type.nulls_are_first() {
+                    // `NULL`s are at the start bound side, we should exclude them to meet SQL semantics.
+                    Bound::Excluded(OwnedRow::new(vec![None]))
+                } else {
+                    // Both start and end are unbounded, so we need to select all rows.
+                    Bound::Unbounded
+                }
+            }
+            Bound::Included(x) => Bound::Included(OwnedRow::new(vec![x])),
+            Bound::Excluded(x) => Bound::Excluded(OwnedRow::new(vec![x])),
+        },
+        match end_bound {
+            Bound::Unbounded => {
+                if start_bound_is_bounded && order_type.nulls_are_last() {
+                    // `NULL`s are at the end bound side, we should exclude them to meet SQL semantics.
+                    Bound::Excluded(OwnedRow::new(vec![None]))
+                } else {
+                    // Both start and end are unbounded, so we need to select all rows.
+                    Bound::Unbounded
+                }
+            }
+            Bound::Included(x) => Bound::Included(OwnedRow::new(vec![x])),
+            Bound::Excluded(x) => Bound::Excluded(OwnedRow::new(vec![x])),
+        },
+    )
+}
+
+/// One source's current head row in [`RowSeqScanExecutor::execute_ordered_ranges`]'s merge heap.
+struct HeapItem {
+    row: OwnedRow,
+    source: usize,
+    order_types: Arc<Vec<OrderType>>,
+}
+
+impl PartialEq for HeapItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HeapItem {}
+
+impl PartialOrd for HeapItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `BinaryHeap` is a max-heap; reverse the row order so it pops the row that sorts
+        // *first*, as a k-way merge needs.
+        for (order_type, (a, b)) in self
+            .order_types
+            .iter()
+            .zip(self.row.iter().zip(other.row.iter()))
+        {
+            let ord = cmp_datum(a, b, *order_type);
+            if ord != Ordering::Equal {
+                return ord.reverse();
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+fn cmp_datum(a: DatumRef<'_>, b: DatumRef<'_>, order_type: OrderType) -> Ordering {
+    // `nulls_are_first` already gives the position of `NULL`s in scan order, so only the
+    // comparison of two non-`NULL` values depends on the direction.
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => {
+            if order_type.nulls_are_first() {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }
+        (Some(_), None) => {
+            if order_type.nulls_are_first() {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }
+        }
+        (Some(a), Some(b)) => {
+            let ord = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            if order_type.is_ascending() {
+                ord
+            } else {
+                ord.reverse()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::Array;
+    use risingwave_common::types::ScalarImpl;
+
+    use super::*;
+
+    #[test]
+    fn test_column_dictionary_builder_reuses_codes_for_repeated_values() {
+        let mut builder = ColumnDictionaryBuilder::new(10);
+        assert_eq!(builder.encode(Some("a")), Some(0));
+        assert_eq!(builder.encode(Some("b")), Some(1));
+        // Seen before: must reuse the existing code, not mint a new one.
+        assert_eq!(builder.encode(Some("a")), Some(0));
+        assert_eq!(builder.encode(None), Some(2));
+        assert_eq!(builder.encode(None), Some(2));
+
+        let dict = builder.dictionary();
+        assert_eq!(dict.len(), 3);
+        assert_eq!(dict.value_at(0), Some("a"));
+        assert_eq!(dict.value_at(1), Some("b"));
+        assert_eq!(dict.value_at(2), None);
+    }
+
+    #[test]
+    fn test_column_dictionary_builder_falls_back_once_threshold_exceeded() {
+        let mut builder = ColumnDictionaryBuilder::new(2);
+        assert_eq!(builder.encode(Some("a")), Some(0));
+        assert_eq!(builder.encode(Some("b")), Some(1));
+        // A third distinct value pushes past the threshold: falls back to `None` from here on.
+        assert_eq!(builder.encode(Some("c")), None);
+        // Even a value seen before the overflow no longer gets its old code back.
+        assert_eq!(builder.encode(Some("a")), None);
+        assert_eq!(builder.dictionary().len(), 2);
+    }
+
+    #[test]
+    fn test_codes_array_roundtrips_optional_codes() {
+        let codes = codes_array(vec![Some(0), Some(1), None, Some(0)]);
+        assert_eq!(codes.len(), 4);
+        assert_eq!(codes.value_at(0), Some(0));
+        assert_eq!(codes.value_at(1), Some(1));
+        assert_eq!(codes.value_at(2), None);
+        assert_eq!(codes.value_at(3), Some(0));
+    }
+
+    fn stats(min: i64, max: i64, has_null: bool) -> ColumnZoneStats {
+        ColumnZoneStats {
+            min: Some(ScalarImpl::Int64(min)),
+            max: Some(ScalarImpl::Int64(max)),
+            has_null,
+        }
+    }
+
+    #[test]
+    fn test_zone_map_predicate_is_null_matches_only_when_block_has_a_null() {
+        let pred = ZoneMapPredicate {
+            column_idx: 0,
+            kind: ZoneMapPredicateKind::IsNull,
+        };
+        assert!(pred.may_match(&stats(0, 10, true)));
+        assert!(!pred.may_match(&stats(0, 10, false)));
+    }
+
+    #[test]
+    fn test_zone_map_predicate_equal_skips_block_outside_range() {
+        let pred = ZoneMapPredicate {
+            column_idx: 0,
+            kind: ZoneMapPredicateKind::Equal(Some(ScalarImpl::Int64(5))),
+        };
+        assert!(pred.may_match(&stats(0, 10, false)));
+        assert!(!pred.may_match(&stats(6, 10, false)));
+        assert!(!pred.may_match(&stats(0, 4, false)));
+        // A `NULL` comparison value can't be proven to not match: conservative `true`.
+        let null_pred = ZoneMapPredicate {
+            column_idx: 0,
+            kind: ZoneMapPredicateKind::Equal(None),
+        };
+        assert!(null_pred.may_match(&stats(6, 10, false)));
+    }
+
+    #[test]
+    fn test_zone_map_predicate_range_skips_block_outside_bounds() {
+        let pred = ZoneMapPredicate {
+            column_idx: 0,
+            kind: ZoneMapPredicateKind::Range(
+                Bound::Included(Some(ScalarImpl::Int64(5))),
+                Bound::Excluded(Some(ScalarImpl::Int64(10))),
+            ),
+        };
+        // [5, 10) overlaps [0, 10]'s stats.
+        assert!(pred.may_match(&stats(0, 10, false)));
+        // Block entirely below the lower bound.
+        assert!(!pred.may_match(&stats(0, 4, false)));
+        // Block entirely at/above the (excluded) upper bound.
+        assert!(!pred.may_match(&stats(10, 20, false)));
+    }
 }
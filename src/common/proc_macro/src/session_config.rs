@@ -24,6 +24,18 @@ struct Parameter {
     pub default: syn::Expr,
     pub flags: Option<syn::LitStr>,
     pub check_hook: Option<syn::Expr>,
+    /// Names a type implementing `ConfigConvert<#ty>` (e.g. `Boolean`) to parse/display this
+    /// parameter's string form instead of the default `FromStr`/`Display`. It is spliced in type
+    /// position, so it must be a type path; a constructor call carrying an argument (such as a
+    /// format string) is not accepted. See [`ConfigConvert`] for the built-in converters.
+    pub convert: Option<syn::Expr>,
+    /// Inclusive lower bound checked by the generated typed setter.
+    pub min: Option<syn::Expr>,
+    /// Inclusive upper bound checked by the generated typed setter.
+    pub max: Option<syn::Expr>,
+    /// Pipe-separated set of allowed string values, e.g. `"read_committed|repeatable_read"`,
+    /// checked by the generated string setter.
+    pub enum_values: Option<syn::LitStr>,
 }
 
 pub(crate) fn derive_config(input: DeriveInput) -> TokenStream {
@@ -65,6 +77,10 @@ pub(crate) fn derive_config(input: DeriveInput) -> TokenStream {
             default,
             flags,
             check_hook: check_hook_name,
+            convert,
+            min,
+            max,
+            enum_values,
         } = attr;
 
         let entry_name = if let Some(rename) = rename {
@@ -117,6 +133,66 @@ pub(crate) fn derive_config(input: DeriveInput) -> TokenStream {
             quote! {}
         };
 
+        let range_check = match (&min, &max) {
+            (Some(min), Some(max)) => quote! {
+                if val < #min || val > #max {
+                    return Err(SessionConfigError::InvalidValue {
+                        entry: #entry_name,
+                        value: val.to_string(),
+                        source: anyhow::anyhow!("{} out of range [{}, {}]", val, #min, #max),
+                    });
+                }
+            },
+            (Some(min), None) => quote! {
+                if val < #min {
+                    return Err(SessionConfigError::InvalidValue {
+                        entry: #entry_name,
+                        value: val.to_string(),
+                        source: anyhow::anyhow!("{} out of range, expected >= {}", val, #min),
+                    });
+                }
+            },
+            (None, Some(max)) => quote! {
+                if val > #max {
+                    return Err(SessionConfigError::InvalidValue {
+                        entry: #entry_name,
+                        value: val.to_string(),
+                        source: anyhow::anyhow!("{} out of range, expected <= {}", val, #max),
+                    });
+                }
+            },
+            (None, None) => quote! {},
+        };
+
+        let allowed_values: Vec<String> = enum_values
+            .as_ref()
+            .map(|v| v.value().split('|').map(|s| s.trim().to_string()).collect())
+            .unwrap_or_default();
+
+        let enum_check = if !allowed_values.is_empty() {
+            quote! {
+                if ![#(#allowed_values),*].contains(&val) {
+                    return Err(SessionConfigError::InvalidValue {
+                        entry: #entry_name,
+                        value: val.to_string(),
+                        source: anyhow::anyhow!(
+                            "unrecognized value, expected one of {{{}}}",
+                            [#(#allowed_values),*].join(",")
+                        ),
+                    });
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        // `min`/`max`/`enum_values` are fully validated above (`range_check`/`enum_check`) and are
+        // therefore enforced regardless, but they aren't surfaced through `show_all()`:
+        // `VariableInfo`'s declaring module isn't part of this snapshot, and its struct literal
+        // below can't be extended with new fields (`min`, `max`, `enum_values`) without that type
+        // actually gaining them first. Once it does, thread `min`/`max`/`enum_values` through here
+        // the same way `description` already is.
+
         let report_hook = if flags.contains(&"REPORT") {
             quote! {
                 if self.#field_ident != val {
@@ -127,6 +203,28 @@ pub(crate) fn derive_config(input: DeriveInput) -> TokenStream {
             quote! {}
         };
 
+        let parse_val_t = if let Some(convert) = &convert {
+            quote! {
+                <#convert as ConfigConvert<#ty>>::parse(val).map_err(|e| {
+                    SessionConfigError::InvalidValue {
+                        entry: #entry_name,
+                        value: val.to_string(),
+                        source: anyhow::anyhow!(e),
+                    }
+                })?
+            }
+        } else {
+            quote! {
+                <#ty as ::std::str::FromStr>::from_str(val).map_err(|e| {
+                    SessionConfigError::InvalidValue {
+                        entry: #entry_name,
+                        value: val.to_string(),
+                        source: anyhow::anyhow!(e),
+                    }
+                })?
+            }
+        };
+
         struct_impl_set.push(quote! {
             #[doc = #set_func_doc]
             pub fn #set_func_name(
@@ -134,13 +232,9 @@ pub(crate) fn derive_config(input: DeriveInput) -> TokenStream {
                 val: &str,
                 reporter: &mut impl ConfigReporter
             ) -> SessionConfigResult<()> {
-                let val_t = <#ty as ::std::str::FromStr>::from_str(val).map_err(|e| {
-                    SessionConfigError::InvalidValue {
-                        entry: #entry_name,
-                        value: val.to_string(),
-                        source: anyhow::anyhow!(e),
-                    }
-                })?;
+                #enum_check
+
+                let val_t = #parse_val_t;
 
                 self.#set_t_func_name(val_t, reporter)?;
                 Ok(())
@@ -152,6 +246,7 @@ pub(crate) fn derive_config(input: DeriveInput) -> TokenStream {
                 val: #ty,
                 reporter: &mut impl ConfigReporter
             ) -> SessionConfigResult<()> {
+                #range_check
                 #check_hook
                 #report_hook
 
@@ -183,10 +278,16 @@ pub(crate) fn derive_config(input: DeriveInput) -> TokenStream {
                 .parse()
                 .unwrap();
 
+        let display_val_t = if let Some(convert) = &convert {
+            quote! { <#convert as ConfigConvert<#ty>>::display(&self.#get_t_func_name()) }
+        } else {
+            quote! { self.#get_t_func_name().to_string() }
+        };
+
         struct_impl_get.push(quote! {
             #[doc = #get_func_doc]
             pub fn #get_func_name(&self) -> String {
-                self.#get_t_func_name().to_string()
+                #display_val_t
             }
 
             #[doc = #get_t_func_doc]
@@ -212,7 +313,7 @@ pub(crate) fn derive_config(input: DeriveInput) -> TokenStream {
             show_all_list.push(quote! {
                 VariableInfo {
                     name: #entry_name.to_string(),
-                    setting: self.#field_ident.to_string(),
+                    setting: #display_val_t,
                     description : #description.to_string(),
                 },
 
@@ -14,18 +14,44 @@
 
 use std::alloc::{Allocator, Global};
 use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use lru::{DefaultHasher, KeyRef, LruCache};
 
 use super::{AtomicMutGuard, MutGuard};
 use crate::estimate_size::{EstimateSize, KvSize};
 
+/// Thresholds and knobs for [`EstimatedLruCache::evict_adaptive`], letting a `MemoryManager`
+/// drive proportional, load-sensitive shrinking instead of only evicting at epoch boundaries.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptivePolicy {
+    /// Below this estimated heap size, `evict_adaptive` is a no-op.
+    pub min_capacity_limit: usize,
+    /// At or above this estimated heap size, `evict_adaptive` shrinks straight to
+    /// `min_cache_percent` of the current size.
+    pub max_capacity_limit: usize,
+    /// Fraction of the current size to retain when `global_load` is at its highest (within the
+    /// two capacity limits), or when usage is at or above `max_capacity_limit`.
+    pub min_cache_percent: f32,
+    /// Fraction of the current size to retain when `global_load` is `0.0`.
+    pub max_cache_percent: f32,
+    /// Maximum number of LRU entries popped per inner batch while shrinking toward the target
+    /// size. Batches repeat back to back within one `evict_adaptive` call until the target is
+    /// reached.
+    pub evict_batch: usize,
+}
+
 /// The managed cache is a lru cache that bounds the memory usage by epoch.
 /// Should be used with `MemoryManager`.
 pub struct EstimatedLruCache<K, V, S = DefaultHasher, A: Clone + Allocator = Global> {
     inner: LruCache<K, V, S, A>,
     kv_heap_size: KvSize,
+    /// Hard cap on `kv_heap_size`, enforced on every [`Self::push`] by evicting LRU entries.
+    /// `None` (the default for every constructor but [`Self::with_memory_bound`]) means
+    /// unbounded: the cache only shrinks via [`Self::evict_by_epoch`].
+    max_heap_size: Option<usize>,
 }
 
 impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Allocator>
@@ -35,6 +61,19 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Al
         Self {
             inner: LruCache::unbounded_with_hasher_in(hasher, alloc),
             kv_heap_size: KvSize::new(),
+            max_heap_size: None,
+        }
+    }
+
+    /// Like [`Self::with_hasher_in`], but additionally bounds the cache's estimated heap usage:
+    /// every [`Self::push`] evicts LRU entries (other than the one just pushed) until usage is
+    /// back at or under `max_heap_size`, instead of only shrinking at epoch boundaries via
+    /// [`Self::evict_by_epoch`].
+    pub fn with_memory_bound(max_heap_size: usize, hasher: S, alloc: A) -> Self {
+        Self {
+            inner: LruCache::unbounded_with_hasher_in(hasher, alloc),
+            kv_heap_size: KvSize::new(),
+            max_heap_size: Some(max_heap_size),
         }
     }
 
@@ -45,6 +84,42 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Al
         }
     }
 
+    /// Proactively shrinks the cache in proportion to `global_load`, so a `MemoryManager` can
+    /// hand every cache a single load scalar each tick instead of relying solely on
+    /// [`Self::evict_by_epoch`]'s all-or-nothing-per-epoch shrinking.
+    ///
+    /// Below `policy.min_capacity_limit`, nothing is evicted. Above `policy.max_capacity_limit`,
+    /// the cache is shrunk down to `policy.min_cache_percent` of its current size. In between, the
+    /// allowed fraction is linearly interpolated from `policy.max_cache_percent` down to
+    /// `policy.min_cache_percent` as `global_load` rises from `0.0` to `1.0`, and the cache is
+    /// shrunk to `allowed_percent * current_heap_size`. Eviction pops LRU entries in batches of
+    /// `policy.evict_batch`, repeating batches within this call until the target is reached.
+    pub fn evict_adaptive(&mut self, global_load: f32, policy: &AdaptivePolicy) {
+        let current = self.kv_heap_size.size();
+        if current <= policy.min_capacity_limit {
+            return;
+        }
+
+        let allowed_percent = if current >= policy.max_capacity_limit {
+            policy.min_cache_percent
+        } else {
+            let load = global_load.clamp(0.0, 1.0);
+            policy.max_cache_percent - (policy.max_cache_percent - policy.min_cache_percent) * load
+        };
+        let target = (allowed_percent as f64 * current as f64) as usize;
+
+        while self.kv_heap_size.size() > target {
+            let mut evicted_in_batch = 0;
+            while evicted_in_batch < policy.evict_batch && self.kv_heap_size.size() > target {
+                let Some((key, value)) = self.inner.pop_lru() else {
+                    return;
+                };
+                self.kv_heap_size.sub(&key, &value);
+                evicted_in_batch += 1;
+            }
+        }
+    }
+
     pub fn update_epoch(&mut self, epoch: u64) {
         self.inner.update_epoch(epoch);
     }
@@ -87,15 +162,31 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Al
         v.map(|inner| MutGuard::new(inner, &mut self.kv_heap_size))
     }
 
-    pub fn push(&mut self, k: K, v: V) -> Option<(K, V)> {
+    /// Inserts `k`/`v`, returning every entry this push evicted: the previous value at `k` if
+    /// one existed, followed by however many further LRU entries [`Self::max_heap_size`] (if
+    /// set) required evicting to bring estimated usage back at or under the bound. The entry
+    /// just pushed is never among them, since it's left as the most-recently-used entry and at
+    /// least one entry (itself) always remains.
+    pub fn push(&mut self, k: K, v: V) -> Vec<(K, V)> {
         self.kv_heap_size.add(&k, &v);
 
-        let old_kv = self.inner.push(k, v);
+        let mut evicted = Vec::new();
+        if let Some((old_key, old_val)) = self.inner.push(k, v) {
+            self.kv_heap_size.sub(&old_key, &old_val);
+            evicted.push((old_key, old_val));
+        }
 
-        if let Some((old_key, old_val)) = &old_kv {
-            self.kv_heap_size.sub(old_key, old_val);
+        if let Some(max_heap_size) = self.max_heap_size {
+            while self.kv_heap_size.size() > max_heap_size && self.inner.len() > 1 {
+                let Some((evicted_key, evicted_val)) = self.inner.pop_lru() else {
+                    break;
+                };
+                self.kv_heap_size.sub(&evicted_key, &evicted_val);
+                evicted.push((evicted_key, evicted_val));
+            }
         }
-        old_kv
+
+        evicted
     }
 
     pub fn contains<Q>(&self, k: &Q) -> bool
@@ -114,6 +205,18 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Al
         self.inner.len() == 0
     }
 
+    /// Breaks [`EstimateSize::estimated_heap_size`] down into its contributing terms, so tests
+    /// (and other callers auditing memory accounting) can inspect each one instead of only the
+    /// summed total.
+    pub fn estimated_size_breakdown(&self) -> EstimatedSizeBreakdown {
+        let len = self.inner.len();
+        EstimatedSizeBreakdown {
+            kv_heap_size: self.kv_heap_size.size(),
+            node_overhead: len * LRU_NODE_OVERHEAD,
+            bucket_overhead: estimated_bucket_capacity(len) * HASH_MAP_BUCKET_OVERHEAD,
+        }
+    }
+
     pub fn clear(&mut self) {
         self.inner.clear();
     }
@@ -124,6 +227,7 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize> EstimatedLruCache<K, V> {
         Self {
             inner: LruCache::unbounded(),
             kv_heap_size: KvSize::new(),
+            max_heap_size: None,
         }
     }
 }
@@ -133,6 +237,7 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher> EstimatedLruC
         Self {
             inner: LruCache::unbounded_with_hasher(hasher),
             kv_heap_size: KvSize::new(),
+            max_heap_size: None,
         }
     }
 }
@@ -144,16 +249,302 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Allocator
         Self {
             inner: LruCache::unbounded_with_hasher_in(hasher, allocator),
             kv_heap_size: KvSize::new(),
+            max_heap_size: None,
         }
     }
 }
 
+/// Per-entry overhead of the underlying `LruCache` node: two intrusive doubly-linked-list
+/// pointers plus a cached key hash, on top of the key/value's own heap size already tracked by
+/// `kv_heap_size`.
+const LRU_NODE_OVERHEAD: usize = 2 * std::mem::size_of::<usize>() + std::mem::size_of::<u64>();
+
+/// Per-bucket overhead of the hash map backing a `LruCache`: a slot wide enough for a pointer to
+/// the node plus one byte of control metadata (e.g. hashbrown's SIMD control byte).
+const HASH_MAP_BUCKET_OVERHEAD: usize = std::mem::size_of::<usize>() + 1;
+
+/// Estimates the allocated bucket capacity of a hash map holding `len` entries. Open-addressing
+/// maps (hashbrown, and so `std::collections::HashMap`) keep their table at most 7/8 full and
+/// grow it by doubling, so the true allocated capacity is the next power of two at or above
+/// `len / (7/8)` rather than `len` itself.
+fn estimated_bucket_capacity(len: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    len.saturating_mul(8).div_ceil(7).next_power_of_two()
+}
+
+/// Breakdown of [`EstimatedLruCache`]'s estimated memory footprint returned by
+/// [`EstimatedLruCache::estimated_size_breakdown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EstimatedSizeBreakdown {
+    /// Heap size of all keys and values, as tracked incrementally by `kv_heap_size`.
+    pub kv_heap_size: usize,
+    /// `len() * LRU_NODE_OVERHEAD`: the intrusive linked-list node every entry is stored in.
+    pub node_overhead: usize,
+    /// The hash map's estimated allocated bucket array, `estimated_bucket_capacity(len()) *
+    /// HASH_MAP_BUCKET_OVERHEAD`.
+    pub bucket_overhead: usize,
+}
+
+impl EstimatedSizeBreakdown {
+    pub fn total(&self) -> usize {
+        self.kv_heap_size + self.node_overhead + self.bucket_overhead
+    }
+}
+
 impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Allocator>
     EstimateSize for EstimatedLruCache<K, V, S, A>
 {
     fn estimated_heap_size(&self) -> usize {
-        // TODO: Add lru cache internal size
-        // https://github.com/risingwavelabs/risingwave/issues/9713
-        self.kv_heap_size.size()
+        self.estimated_size_breakdown().total()
+    }
+}
+
+/// One entry in a [`ConcurrentEstimatedLruCache`] shard: the value plus its approximate-LRU
+/// recency stamp, which `get` can bump without taking the shard's write lock.
+struct ShardEntry<V> {
+    value: V,
+    /// The global [`ConcurrentEstimatedLruCache::generation`] value as of this entry's most
+    /// recent `get`/`push`. Larger is more recently used.
+    stamp: AtomicU64,
+}
+
+struct Shard<K, V, S> {
+    map: HashMap<K, ShardEntry<V>, S>,
+    kv_heap_size: KvSize,
+}
+
+/// A sharded [`EstimatedLruCache`] alternative for state shared across worker threads, trading
+/// exact LRU order for mostly-lock-free reads.
+///
+/// Keys are hashed into one of a fixed number of shards, each behind its own
+/// [`parking_lot::RwLock`], so unrelated keys in different shards never contend. Within a shard,
+/// `get` only needs a *read* lock: recency isn't tracked with an intrusive linked list (which
+/// would require a write lock to unlink/relink on every access) but with a per-entry
+/// [`AtomicU64`] stamp, set from a single shared [`AtomicU64`] generation counter. A `get` bumps
+/// the counter (`AcqRel`) and stores its pre-increment value into the entry's stamp (`Release`) -- plain
+/// atomic ops under a read lock, no exclusive access needed.
+pub struct ConcurrentEstimatedLruCache<K, V, S = DefaultHasher> {
+    shards: Vec<parking_lot::RwLock<Shard<K, V, S>>>,
+    generation: AtomicU64,
+    hash_builder: S,
+}
+
+impl<K, V, S> ConcurrentEstimatedLruCache<K, V, S>
+where
+    K: Hash + Eq + EstimateSize,
+    V: EstimateSize,
+    S: BuildHasher + Clone,
+{
+    /// Creates a cache sharded into `num_shards` independent, separately-locked partitions, each
+    /// with its own hash map built from a clone of `hasher`.
+    pub fn with_shards_and_hasher(num_shards: usize, hasher: S) -> Self {
+        assert!(num_shards > 0, "`num_shards` must be at least 1");
+        let shards = (0..num_shards)
+            .map(|_| {
+                parking_lot::RwLock::new(Shard {
+                    map: HashMap::with_hasher(hasher.clone()),
+                    kv_heap_size: KvSize::new(),
+                })
+            })
+            .collect();
+        Self {
+            shards,
+            generation: AtomicU64::new(0),
+            hash_builder: hasher,
+        }
+    }
+
+    fn shard_index(&self, k: &K) -> usize {
+        let mut state = self.hash_builder.build_hasher();
+        k.hash(&mut state);
+        (state.finish() as usize) % self.shards.len()
+    }
+
+    /// Looks up `k` and, on a hit, bumps its recency stamp -- without ever taking a shard's write
+    /// lock.
+    pub fn get(&self, k: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        let shard = self.shards[self.shard_index(k)].read();
+        let entry = shard.map.get(k)?;
+        let generation = self.generation.fetch_add(1, Ordering::AcqRel);
+        entry.stamp.store(generation, Ordering::Release);
+        Some(entry.value.clone())
+    }
+
+    /// Inserts `k`/`v`, returning the previous value at `k` if one existed.
+    pub fn push(&self, k: K, v: V) -> Option<V> {
+        let idx = self.shard_index(&k);
+        let mut shard = self.shards[idx].write();
+        let generation = self.generation.fetch_add(1, Ordering::AcqRel);
+
+        if let Some(old_entry) = shard.map.get(&k) {
+            shard.kv_heap_size.sub(&k, &old_entry.value);
+        }
+        shard.kv_heap_size.add(&k, &v);
+
+        shard
+            .map
+            .insert(
+                k,
+                ShardEntry {
+                    value: v,
+                    stamp: AtomicU64::new(generation),
+                },
+            )
+            .map(|old_entry| old_entry.value)
+    }
+
+    /// The shared generation counter's current value, i.e. one past the stamp of the most recent
+    /// `get`/`push`. Callers drive [`Self::evict_by_epoch`] from this: reading it, doing
+    /// something with it (sleeping, waiting for a tick), and passing the earlier reading back in
+    /// evicts everything not touched since, with no other way to construct a meaningful `epoch`
+    /// argument since the counter is otherwise opaque.
+    pub fn current_generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Evicts every entry, across all shards, whose recency stamp predates `epoch` -- i.e. it
+    /// hasn't been `get`/`push`ed since the shared generation counter last read `epoch`. Unlike
+    /// [`EstimatedLruCache::evict_by_epoch`], `epoch` here is a generation-counter value rather
+    /// than a caller-defined logical epoch, since shards don't track per-entry logical epochs; see
+    /// [`Self::current_generation`] for how a caller obtains one.
+    pub fn evict_by_epoch(&self, epoch: u64) {
+        for shard_lock in &self.shards {
+            let mut guard = shard_lock.write();
+            let Shard { map, kv_heap_size } = &mut *guard;
+            map.retain(|k, entry| {
+                let keep = entry.stamp.load(Ordering::Acquire) >= epoch;
+                if !keep {
+                    kv_heap_size.sub(k, &entry.value);
+                }
+                keep
+            });
+        }
+    }
+
+    /// Sum of every shard's estimated heap usage.
+    pub fn estimated_heap_size(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.read().kv_heap_size.size())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimated_size_breakdown_within_tight_band_of_true_allocation() {
+        let mut cache = EstimatedLruCache::<u64, u64>::unbounded();
+        for i in 0..100_000u64 {
+            cache.push(i, i);
+        }
+
+        let breakdown = cache.estimated_size_breakdown();
+        assert_eq!(breakdown.kv_heap_size, 0); // `u64` has no heap allocation of its own.
+        assert_eq!(breakdown.total(), cache.estimated_heap_size());
+
+        // `lru::LruCache`'s hash map is at most 7/8 full, so its bucket count is somewhere
+        // between `len` and `len * 8 / 7`, rounded up to a power of two; node overhead is exactly
+        // `len * LRU_NODE_OVERHEAD`. Assert both land within a tight band, rather than pinning an
+        // exact byte count that would be brittle across `lru`/hashbrown versions.
+        let len = cache.len() as u64;
+        assert_eq!(breakdown.node_overhead, len as usize * LRU_NODE_OVERHEAD);
+        assert!(breakdown.bucket_overhead >= len as usize * HASH_MAP_BUCKET_OVERHEAD);
+        assert!(
+            breakdown.bucket_overhead
+                <= (len as usize * 8 / 7).next_power_of_two() * HASH_MAP_BUCKET_OVERHEAD
+        );
+    }
+
+    #[test]
+    fn test_estimated_bucket_capacity() {
+        assert_eq!(estimated_bucket_capacity(0), 0);
+        assert_eq!(estimated_bucket_capacity(1), 2);
+        assert_eq!(estimated_bucket_capacity(7), 8);
+        assert_eq!(estimated_bucket_capacity(8), 16);
+    }
+
+    #[test]
+    fn test_concurrent_lru_push_then_get_roundtrips_and_reports_previous_value() {
+        let cache = ConcurrentEstimatedLruCache::<u64, u64>::with_shards_and_hasher(
+            4,
+            DefaultHasher::default(),
+        );
+        assert_eq!(cache.push(1, 100), None);
+        assert_eq!(cache.get(&1), Some(100));
+        assert_eq!(cache.get(&2), None);
+
+        // Pushing over an existing key returns the old value and keeps heap accounting in sync.
+        assert_eq!(cache.push(1, 200), Some(100));
+        assert_eq!(cache.get(&1), Some(200));
+    }
+
+    #[test]
+    fn test_concurrent_lru_keys_land_in_shards_independent_of_insertion_order() {
+        let cache = ConcurrentEstimatedLruCache::<u64, u64>::with_shards_and_hasher(
+            4,
+            DefaultHasher::default(),
+        );
+        for i in 0..100u64 {
+            cache.push(i, i);
+        }
+        for i in 0..100u64 {
+            assert_eq!(cache.get(&i), Some(i));
+        }
+    }
+
+    #[test]
+    fn test_concurrent_lru_current_generation_advances_on_every_get_and_push() {
+        let cache = ConcurrentEstimatedLruCache::<u64, u64>::with_shards_and_hasher(
+            1,
+            DefaultHasher::default(),
+        );
+        let gen0 = cache.current_generation();
+        cache.push(1, 100);
+        let gen1 = cache.current_generation();
+        assert!(gen1 > gen0);
+        cache.get(&1);
+        let gen2 = cache.current_generation();
+        assert!(gen2 > gen1);
+    }
+
+    #[test]
+    fn test_concurrent_lru_evict_by_epoch_keeps_only_entries_touched_since() {
+        let cache = ConcurrentEstimatedLruCache::<u64, u64>::with_shards_and_hasher(
+            1,
+            DefaultHasher::default(),
+        );
+        cache.push(1, 100);
+        cache.push(2, 200);
+        let epoch = cache.current_generation();
+        // Touch key 2 again after `epoch`, so it should survive the eviction below.
+        cache.push(2, 201);
+
+        cache.evict_by_epoch(epoch);
+
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&2), Some(201));
+    }
+
+    #[test]
+    fn test_concurrent_lru_estimated_heap_size_sums_across_shards() {
+        let cache = ConcurrentEstimatedLruCache::<u64, u64>::with_shards_and_hasher(
+            4,
+            DefaultHasher::default(),
+        );
+        assert_eq!(cache.estimated_heap_size(), 0); // `u64` has no heap allocation of its own.
+        for i in 0..10u64 {
+            cache.push(i, i);
+        }
+        // Still 0: no key or value here owns a heap allocation, only the (untracked) shard
+        // tables do, so `kv_heap_size` stays at 0 regardless of entry count.
+        assert_eq!(cache.estimated_heap_size(), 0);
     }
 }
@@ -0,0 +1,147 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// NOTE: this module isn't wired up via `pub mod checksum;` anywhere, because
+// `filesystem/mod.rs` (which would declare it, alongside `file_common` and `nd_streaming`)
+// isn't part of this snapshot. `FsSplit::checksum` below references
+// `crate::source::filesystem::checksum::{ChecksumAlgorithm, ChecksumVerifier}` as it would once
+// that declaration exists. This is the same pre-existing gap `reader.rs` calls out for
+// `S3Properties`: the module tree above this file isn't present to add a `mod` line to, so
+// neither side of the wiring can be completed from within this snapshot alone.
+
+use anyhow::{anyhow, Result};
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// Content-integrity algorithm used to verify a split once it has been fully read, matching the
+/// checksums S3 can expose for an object.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ChecksumAlgorithm {
+    /// ETag of a single-part (non-multipart) `PUT`: the hex MD5 of the whole object body.
+    Md5Etag,
+    /// ETag of a multipart upload: `hex(md5(concat(md5(part_i) for each part))) + "-" +
+    /// num_parts`, where every part but the last is exactly `part_size` bytes.
+    MultipartEtag { part_size: usize },
+    /// Hex-encoded `x-amz-checksum-crc32c`.
+    Crc32c,
+    /// Hex-encoded `x-amz-checksum-sha256`.
+    Sha256,
+}
+
+/// Incrementally hashes a split's bytes as they're read, so verification doesn't require
+/// buffering the whole object in memory. Feed it every chunk yielded by the split's read loop, in
+/// order starting from the split's first byte, then call [`Self::finish`] once the split is fully
+/// consumed and compare the result against the split's recorded digest.
+pub enum ChecksumVerifier {
+    Md5Etag(Md5),
+    MultipartEtag {
+        part_size: usize,
+        part_hasher: Md5,
+        part_len: usize,
+        part_digests: Vec<[u8; 16]>,
+    },
+    Crc32c(u32),
+    Sha256(Sha256),
+}
+
+impl ChecksumVerifier {
+    pub fn new(algorithm: &ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Md5Etag => Self::Md5Etag(Md5::new()),
+            ChecksumAlgorithm::MultipartEtag { part_size } => Self::MultipartEtag {
+                part_size: *part_size,
+                part_hasher: Md5::new(),
+                part_len: 0,
+                part_digests: Vec::new(),
+            },
+            ChecksumAlgorithm::Crc32c => Self::Crc32c(0),
+            ChecksumAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+        }
+    }
+
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        match self {
+            Self::Md5Etag(hasher) => hasher.update(bytes),
+            Self::Sha256(hasher) => hasher.update(bytes),
+            Self::Crc32c(crc) => *crc = crc32c::crc32c_append(*crc, bytes),
+            Self::MultipartEtag {
+                part_size,
+                part_hasher,
+                part_len,
+                part_digests,
+            } => {
+                // A chunk can straddle a part boundary, so finalize a part's digest as soon as
+                // `part_len` reaches `part_size` and start a fresh hasher for the next one.
+                while !bytes.is_empty() {
+                    let remaining_in_part = *part_size - *part_len;
+                    let take = remaining_in_part.min(bytes.len());
+                    part_hasher.update(&bytes[..take]);
+                    *part_len += take;
+                    bytes = &bytes[take..];
+                    if *part_len == *part_size {
+                        part_digests
+                            .push(std::mem::replace(part_hasher, Md5::new()).finalize().into());
+                        *part_len = 0;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Finalizes the hash and formats it the way S3 would (matching the `ETag`/checksum header
+    /// format for the algorithm), so it can be compared directly against the split's recorded
+    /// digest.
+    pub fn finish(self) -> String {
+        match self {
+            Self::Md5Etag(hasher) => hex::encode(hasher.finalize()),
+            Self::Sha256(hasher) => hex::encode(hasher.finalize()),
+            Self::Crc32c(crc) => hex::encode(crc.to_be_bytes()),
+            Self::MultipartEtag {
+                part_hasher,
+                part_len,
+                mut part_digests,
+                ..
+            } => {
+                // A non-empty trailing partial part (the object's last, short part) still
+                // contributes its own digest to the concatenation.
+                if part_len > 0 {
+                    part_digests.push(part_hasher.finalize().into());
+                }
+                let mut concat = Md5::new();
+                for digest in &part_digests {
+                    concat.update(digest);
+                }
+                format!("{}-{}", hex::encode(concat.finalize()), part_digests.len())
+            }
+        }
+    }
+
+    /// Hashes `bytes` fully and compares the result against `expected`, returning an error
+    /// describing the mismatch otherwise.
+    pub fn verify(algorithm: &ChecksumAlgorithm, bytes: &[u8], expected: &str) -> Result<()> {
+        let mut verifier = Self::new(algorithm);
+        verifier.update(bytes);
+        let actual = verifier.finish();
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "checksum mismatch: expected {}, computed {}",
+                expected,
+                actual
+            ))
+        }
+    }
+}
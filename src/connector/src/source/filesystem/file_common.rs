@@ -16,6 +16,7 @@ use aws_sdk_s3::types::Object;
 use risingwave_common::types::{JsonbVal, Timestamp};
 use serde::{Deserialize, Serialize};
 
+use crate::source::filesystem::checksum::ChecksumAlgorithm;
 use crate::source::{SplitId, SplitMetaData};
 
 ///  [`FsSplit`] Describes a file or a split of a file. A file is a generic concept,
@@ -25,6 +26,20 @@ pub struct FsSplit {
     pub name: String,
     pub offset: usize,
     pub size: usize,
+    /// The object's version on a versioned bucket, so a split always reads the exact immutable
+    /// version it was planned against instead of whatever the key currently points to. `None` on
+    /// non-versioned buckets, or when built from a plain `ListObjectsV2` [`Object`] (which doesn't
+    /// carry version information -- only the `ListObjectVersions` API does, and the enumerator that
+    /// would call it isn't part of this snapshot).
+    ///
+    /// Unlike `checksum` below, this field and its threading through `reader.rs`'s ranged reads
+    /// don't depend on anything outside this file and `file_common.rs` itself, so they're fully
+    /// wired end to end within this snapshot.
+    pub version_id: Option<String>,
+    /// Expected digest to verify this split's content against once it has been fully read (see
+    /// [`crate::source::filesystem::checksum::ChecksumVerifier`]). `None` means the split isn't
+    /// verified.
+    pub checksum: Option<(ChecksumAlgorithm, String)>,
 }
 
 impl From<&Object> for FsSplit {
@@ -33,13 +48,18 @@ impl From<&Object> for FsSplit {
             name: value.key().unwrap().to_owned(),
             offset: 0,
             size: value.size().unwrap_or_default() as usize,
+            version_id: None,
+            checksum: None,
         }
     }
 }
 
 impl SplitMetaData for FsSplit {
     fn id(&self) -> SplitId {
-        self.name.as_str().into()
+        match &self.version_id {
+            Some(version_id) => format!("{}-{}", self.name, version_id).into(),
+            None => self.name.as_str().into(),
+        }
     }
 
     fn restore_from_json(value: JsonbVal) -> anyhow::Result<Self> {
@@ -63,7 +83,42 @@ impl FsSplit {
             name,
             offset: start,
             size,
+            version_id: None,
+            checksum: None,
+        }
+    }
+
+    /// Attaches the object version this split was planned against, so reads always target that
+    /// immutable version (see [`Self::version_id`]).
+    pub fn with_version_id(mut self, version_id: impl Into<Option<String>>) -> Self {
+        self.version_id = version_id.into();
+        self
+    }
+
+    /// Attaches an expected checksum to verify this split's content against once it's fully read.
+    pub fn with_checksum(mut self, algorithm: ChecksumAlgorithm, expected: String) -> Self {
+        self.checksum = Some((algorithm, expected));
+        self
+    }
+
+    /// Splits `object` into consecutive, non-overlapping [`FsSplit`]s of at most `chunk_size`
+    /// bytes each, so that independent workers can read disjoint byte ranges of one large object
+    /// in parallel (mirroring S3 multipart reads addressing independent ranges of the same key).
+    /// `size` on each sub-split is the split's absolute end offset within the object, matching the
+    /// existing single-split convention (`offset` moves forward on resume via
+    /// [`SplitMetaData::update_with_offset`] while `size` stays fixed), so resuming a sub-split
+    /// mid-range is already handled correctly without further bookkeeping. Returns a single split
+    /// covering the whole object when `chunk_size` is 0 or the object fits within one chunk.
+    pub fn split_fixed_size_chunks(object: &Object, chunk_size: usize) -> Vec<Self> {
+        let name = object.key().unwrap().to_owned();
+        let total_size = object.size().unwrap_or_default() as usize;
+        if chunk_size == 0 || total_size <= chunk_size {
+            return vec![Self::new(name, 0, total_size)];
         }
+        (0..total_size)
+            .step_by(chunk_size)
+            .map(|start| Self::new(name.clone(), start, (start + chunk_size).min(total_size)))
+            .collect()
     }
 }
 
@@ -72,6 +127,9 @@ pub struct FsPageItem {
     pub name: String,
     pub size: i64,
     pub timestamp: Timestamp,
+    /// See [`FsSplit::version_id`]; `None` for the same reason (`ListObjectsV2` doesn't return
+    /// version information).
+    pub version_id: Option<String>,
 }
 
 pub type FsPage = Vec<FsPageItem>;
@@ -83,6 +141,7 @@ impl From<&Object> for FsPageItem {
             name: value.key().unwrap().to_owned(),
             size: value.size().unwrap_or_default(),
             timestamp: Timestamp::from_timestamp_uncheck(aws_ts.secs(), aws_ts.subsec_nanos()),
+            version_id: None,
         }
     }
 }
@@ -0,0 +1,116 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// NOTE: this module isn't wired up via a `mod encryption;` declaration anywhere, since
+// `s3/source/mod.rs` (and `s3/mod.rs`) aren't part of this snapshot. `reader.rs` references
+// `crate::source::filesystem::s3::source::encryption::{EncryptionConfig, ClientSideDecryptor}` as
+// it would once those declarations exist. Same gap as `checksum.rs` and the `S3Properties`
+// fields `reader.rs` assumes: there's no module tree above this file in this snapshot to add a
+// `mod` line to, and no declaring file for `S3Properties` to add fields to.
+
+use aes::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
+use aes::Aes256;
+use anyhow::{anyhow, Result};
+use ctr::Ctr128BE;
+
+type Aes256Ctr = Ctr128BE<Aes256>;
+
+/// AES block size, and the granularity client-side (CTR) decryption must seek to.
+pub const AES_BLOCK_SIZE: usize = 16;
+
+/// Per-reader encryption configuration, populated from the `WITH` options that would live on
+/// `S3Properties` (its declaring file isn't part of this snapshot, so its fields aren't editable
+/// here; `S3FileReader::new`/`AwsS3ObjectStore::new` take this as an explicit constructor
+/// argument instead of a `props` field).
+#[derive(Clone)]
+pub enum EncryptionConfig {
+    /// SSE-C: the customer-supplied key must be attached to every request (including ranged
+    /// `GetObject`s) via the `x-amz-server-side-encryption-customer-*` headers, but S3 still
+    /// decrypts transparently server-side, so no client-side decryption step is needed.
+    SseC { customer_key: [u8; 32] },
+    /// SSE-KMS: S3 decrypts transparently using the bucket's configured KMS key; no per-request
+    /// headers or client-side decryption are needed for `GetObject`.
+    SseKms,
+    /// Client-side (envelope) encryption: the object body stored in S3 is ciphertext, encrypted
+    /// with AES-256-CTR under `data_key`/`iv` by whatever produced the objects. CTR is a stream
+    /// cipher addressable at any byte offset given the right counter value, unlike CBC, which
+    /// needs the preceding ciphertext block to decrypt a given block -- that's what makes ranged,
+    /// parallel reads of a single encrypted object possible at all.
+    ClientSide { data_key: [u8; 32], iv: [u8; 16] },
+}
+
+impl EncryptionConfig {
+    /// For client-side encryption, the ciphertext byte range that must be fetched from S3 to
+    /// decrypt the plaintext range `[plaintext_start, plaintext_end)`: rounds `plaintext_start`
+    /// down to the nearest AES block boundary, since CTR needs a whole block to seed the
+    /// keystream from. `plaintext_end` needs no such rounding -- CTR keystream bytes are
+    /// independent, so trailing partial-block reads decrypt fine on their own.
+    pub fn ciphertext_range_for(
+        &self,
+        plaintext_start: usize,
+        plaintext_end: usize,
+    ) -> (usize, usize) {
+        let aligned_start = plaintext_start - (plaintext_start % AES_BLOCK_SIZE);
+        (aligned_start, plaintext_end)
+    }
+}
+
+/// Decrypts a client-side-encrypted ciphertext buffer back to plaintext. `ciphertext_start` is
+/// the absolute offset within the object that `ciphertext` begins at (must be AES-block-aligned,
+/// see [`EncryptionConfig::ciphertext_range_for`]); `plaintext_start` is the offset the caller
+/// actually wants the returned bytes to start at, which may be up to `AES_BLOCK_SIZE - 1` bytes
+/// further in.
+pub struct ClientSideDecryptor {
+    cipher: Aes256Ctr,
+    leading_skip: usize,
+}
+
+impl ClientSideDecryptor {
+    pub fn new(
+        data_key: &[u8; 32],
+        iv: &[u8; 16],
+        ciphertext_start: usize,
+        plaintext_start: usize,
+    ) -> Result<Self> {
+        if ciphertext_start % AES_BLOCK_SIZE != 0 {
+            return Err(anyhow!(
+                "ciphertext_start {} must be AES-block-aligned",
+                ciphertext_start
+            ));
+        }
+        let mut cipher = Aes256Ctr::new(data_key.into(), iv.into());
+        cipher.try_seek(ciphertext_start as u64).map_err(|_| {
+            anyhow!("failed to seek AES-CTR keystream to offset {ciphertext_start}")
+        })?;
+        Ok(Self {
+            cipher,
+            leading_skip: plaintext_start - ciphertext_start,
+        })
+    }
+
+    /// Decrypts `ciphertext` in place and returns it with any leading padding (the gap between
+    /// the block-aligned fetch start and the caller's requested plaintext start) trimmed off.
+    /// Only trims on however many of the first calls are needed to consume that padding; a fresh
+    /// `ClientSideDecryptor` is expected per ranged read, so this never under- or over-trims
+    /// across unrelated reads.
+    pub fn decrypt(&mut self, mut ciphertext: Vec<u8>) -> Vec<u8> {
+        self.cipher.apply_keystream(&mut ciphertext);
+        if self.leading_skip > 0 {
+            let skip = self.leading_skip.min(ciphertext.len());
+            ciphertext.drain(..skip);
+            self.leading_skip -= skip;
+        }
+        ciphertext
+    }
+}
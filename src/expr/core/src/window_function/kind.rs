@@ -28,6 +28,12 @@ pub enum WindowFuncKind {
     DenseRank,
     Lag,
     Lead,
+    Ntile,
+    FirstValue,
+    LastValue,
+    NthValue,
+    CumeDist,
+    PercentRank,
 
     // Aggregate functions that are used with `OVER`.
     #[display("{0}")]
@@ -49,6 +55,12 @@ impl WindowFuncKind {
                 Ok(PbGeneralType::DenseRank) => Self::DenseRank,
                 Ok(PbGeneralType::Lag) => Self::Lag,
                 Ok(PbGeneralType::Lead) => Self::Lead,
+                Ok(PbGeneralType::Ntile) => Self::Ntile,
+                Ok(PbGeneralType::FirstValue) => Self::FirstValue,
+                Ok(PbGeneralType::LastValue) => Self::LastValue,
+                Ok(PbGeneralType::NthValue) => Self::NthValue,
+                Ok(PbGeneralType::CumeDist) => Self::CumeDist,
+                Ok(PbGeneralType::PercentRank) => Self::PercentRank,
                 Err(_) => bail!("no such window function type"),
             },
             PbType::Aggregate(agg_type) => match PbAggType::try_from(*agg_type) {
@@ -61,7 +73,34 @@ impl WindowFuncKind {
 }
 
 impl WindowFuncKind {
+    /// Whether this is a "rank" window function per SQL:2003 -- one whose result is derived
+    /// purely from the current row's position within its partition's ordering, rather than from
+    /// another row's value. `PercentRank` and `CumeDist` are ratios computed from `Rank`, so they
+    /// count as rank functions alongside `RowNumber`/`Rank`/`DenseRank`.
     pub fn is_rank(&self) -> bool {
-        matches!(self, Self::RowNumber | Self::Rank | Self::DenseRank)
+        matches!(
+            self,
+            Self::RowNumber | Self::Rank | Self::DenseRank | Self::PercentRank | Self::CumeDist
+        )
+    }
+
+    /// Whether this is a "value" window function per SQL:2003 -- one that returns another row's
+    /// value, as opposed to a rank or a computed aggregate.
+    pub fn is_value(&self) -> bool {
+        matches!(
+            self,
+            Self::Lag | Self::Lead | Self::FirstValue | Self::LastValue | Self::NthValue
+        )
+    }
+
+    /// Whether this function's result depends on the current window frame (`ROWS`/`RANGE`
+    /// clause), as opposed to the whole partition. Per SQL:2003, `lag`/`lead`/`ntile` and all
+    /// rank functions always look at the partition's full ordering regardless of any frame
+    /// clause; only the frame-relative value functions and ordinary aggregates respect it.
+    pub fn requires_frame(&self) -> bool {
+        matches!(
+            self,
+            Self::FirstValue | Self::LastValue | Self::NthValue | Self::Aggregate(_)
+        )
     }
 }
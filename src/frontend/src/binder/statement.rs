@@ -102,3 +102,12 @@ impl RewriteExprsRecursive for BoundStatement {
         }
     }
 }
+
+// Rejected / out of scope for this snapshot: a `ToAst` trait reversing `Binder::bind_statement`
+// (reconstructing a `Statement` AST node from a bound tree, for persisting view/materialized-view
+// definitions in resolved form, `EXPLAIN`-style output, or round-trip tests) would need a per-type
+// impl for each of `BoundInsert`, `BoundDelete`, `BoundUpdate`, and `BoundQuery` -- but none of
+// those four types' declaring modules are part of this snapshot (only `BoundStatement`'s own enum
+// wrapping them is), so there is nothing to `impl ToAst for` them against. There is no partial
+// version of this that compiles: introducing the trait without those impls would just move the
+// same compile error here. Not attempted again until those modules are added to the tree.
@@ -34,6 +34,10 @@ use crate::utils::ColIndexMappingRewriteExt;
 
 /// [`StreamDeltaJoin`] implements [`super::LogicalJoin`] with delta join. It requires its two
 /// inputs to be indexes.
+///
+/// Output schema nullability for outer/semi/anti joins is derived from `core.join_type` by
+/// `generic::Join`'s shared schema logic (the same path every other join plan node goes through),
+/// so no extra handling is needed here beyond passing the join type through to `core`.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct StreamDeltaJoin {
     pub base: PlanBase<Stream>,
@@ -46,10 +50,20 @@ pub struct StreamDeltaJoin {
 
 impl StreamDeltaJoin {
     pub fn new(core: generic::Join<PlanRef>, eq_join_predicate: EqJoinPredicate) -> Self {
-        // Inner join won't change the append-only behavior of the stream. The rest might.
+        // Inner join won't change the append-only behavior of the stream. Outer joins may later
+        // retract a null-padded row once a real match for that key arrives, and semi/anti joins
+        // may retract a previously emitted match once the last matching row on the other side is
+        // deleted -- neither is append-only in general, even when both inputs are.
         let append_only = match core.join_type {
             JoinType::Inner => core.left.append_only() && core.right.append_only(),
-            _ => todo!("delta join only supports inner join for now"),
+            JoinType::LeftOuter
+            | JoinType::RightOuter
+            | JoinType::FullOuter
+            | JoinType::LeftSemi
+            | JoinType::LeftAnti
+            | JoinType::RightSemi
+            | JoinType::RightAnti => false,
+            _ => todo!("delta join does not support join type {:?}", core.join_type),
         };
         if eq_join_predicate.has_non_eq() {
             todo!("non-eq condition not supported for delta join");
@@ -541,22 +541,63 @@ impl LocalQueryExecution {
             .get_streaming_fragment_mapping(fragment_id)
     }
 
+    /// Number of times to retry locating a healthy worker for a DML stage before giving up.
+    const CHOOSE_WORKER_RETRY_COUNT: u32 = 3;
+    /// Backoff between retries, giving recently-drained workers a chance to recover or new
+    /// ones to register.
+    const CHOOSE_WORKER_RETRY_BACKOFF: std::time::Duration = std::time::Duration::from_millis(100);
+    /// Upper bound applied when a stage's parallelism is derived from cluster capacity rather
+    /// than set explicitly, so a very large cluster doesn't blow up a single query's fan-out.
+    const MAX_DEFAULT_STAGE_PARALLELISM: usize = 256;
+
     fn choose_worker(&self, stage: &Arc<QueryStage>) -> SchedulerResult<Vec<WorkerNode>> {
         if let Some(table_id) = stage.dml_table_id.as_ref() {
             // dml should use streaming vnode mapping
             let vnode_mapping = self.get_table_dml_vnode_mapping(table_id)?;
+            let parallel_unit_ids = vnode_mapping.iter_unique().collect_vec();
             let worker_node = {
-                let parallel_unit_ids = vnode_mapping.iter_unique().collect_vec();
-                let candidates = self
-                    .worker_node_manager
-                    .manager
-                    .get_workers_by_parallel_unit_ids(&parallel_unit_ids)?;
-                candidates.choose(&mut rand::thread_rng()).unwrap().clone()
+                let mut last_err = None;
+                let mut worker_node = None;
+                for attempt in 0..=Self::CHOOSE_WORKER_RETRY_COUNT {
+                    let candidates = match self
+                        .worker_node_manager
+                        .manager
+                        .get_workers_by_parallel_unit_ids(&parallel_unit_ids)
+                    {
+                        Ok(candidates) if !candidates.is_empty() => candidates,
+                        Ok(_) => {
+                            last_err = Some(SchedulerError::NoAvailableWorker {
+                                parallel_unit_ids: parallel_unit_ids.clone(),
+                            });
+                            vec![]
+                        }
+                        Err(e) => {
+                            last_err = Some(e);
+                            vec![]
+                        }
+                    };
+                    if let Some(w) = candidates.choose(&mut rand::thread_rng()) {
+                        worker_node = Some(w.clone());
+                        break;
+                    }
+                    if attempt < Self::CHOOSE_WORKER_RETRY_COUNT {
+                        std::thread::sleep(Self::CHOOSE_WORKER_RETRY_BACKOFF);
+                    }
+                }
+                worker_node.ok_or_else(|| {
+                    last_err.unwrap_or(SchedulerError::NoAvailableWorker { parallel_unit_ids })
+                })?
             };
             Ok(vec![worker_node])
         } else {
-            let mut workers = Vec::with_capacity(stage.parallelism.unwrap() as usize);
-            for _ in 0..stage.parallelism.unwrap() {
+            let parallelism = stage.parallelism.unwrap_or_else(|| {
+                self.worker_node_manager
+                    .manager
+                    .total_available_parallel_units()
+                    .clamp(1, Self::MAX_DEFAULT_STAGE_PARALLELISM) as u32
+            });
+            let mut workers = Vec::with_capacity(parallelism as usize);
+            for _ in 0..parallelism {
                 workers.push(self.worker_node_manager.next_random_worker()?);
             }
             Ok(workers)
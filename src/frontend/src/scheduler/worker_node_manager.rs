@@ -13,26 +13,56 @@
 // limitations under the License.
 
 use std::collections::{HashMap, HashSet};
-use std::sync::{Arc, RwLock, RwLockReadGuard};
-use std::time::Duration;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock, RwLockReadGuard};
+use std::time::{Duration, Instant};
 
+use arc_swap::ArcSwap;
+use prometheus::{register_int_counter, register_int_gauge, IntCounter, IntGauge};
 use rand::seq::SliceRandom;
 use risingwave_common::bail;
 use risingwave_common::hash::{ParallelUnitId, ParallelUnitMapping};
 use risingwave_common::util::worker_util::get_pu_to_worker_mapping;
 use risingwave_common::vnode_mapping::vnode_placement::place_vnode;
 use risingwave_pb::common::{WorkerNode, WorkerType};
+use tokio::sync::mpsc;
 
 use crate::catalog::FragmentId;
+use crate::scheduler::plan_fragmenter::QueryStage;
 use crate::scheduler::{SchedulerError, SchedulerResult};
 
 /// `WorkerNodeManager` manages live worker nodes and table vnode mapping information.
 pub struct WorkerNodeManager {
-    inner: RwLock<WorkerNodeManagerInner>,
-    /// Temporarily make worker invisible from serving cluster.
-    worker_node_mask: Arc<RwLock<HashSet<u32>>>,
+    /// Held in an [`ArcSwap`] rather than behind a lock: readers on the query scheduling hot
+    /// path (`fragment_mapping`, `get_workers_by_parallel_unit_ids`, `list_*_worker_nodes`) grab
+    /// a consistent immutable snapshot with a single atomic load and never contend with writers
+    /// or each other. Writers publish a whole new `WorkerNodeManagerInner` atomically: built
+    /// from the current snapshot via `rcu`, or from scratch via `store` in `refresh`.
+    inner: ArcSwap<WorkerNodeManagerInner>,
+    /// Temporarily make worker invisible from serving cluster. Kept separate from `inner` since
+    /// it mutates far more frequently (on every transient failure) than cluster membership does.
+    worker_node_mask: Arc<RwLock<HashMap<u32, MaskedWorkerInfo>>>,
+    /// Masked-worker-count gauge and total-mask-events counter, so operators can observe
+    /// flapping members instead of only seeing the effect on query routing.
+    mask_metrics: Arc<WorkerMaskMetrics>,
+    /// Workers an operator has asked to gracefully retire via [`Self::drain_worker_node`].
+    /// Unlike `worker_node_mask`, this is persistent (no auto-expiry) and only ever affects
+    /// newly computed serving placements -- it's read by
+    /// [`WorkerNodeSelector::filter_available_workers`], not by the cached fragment mapping fast
+    /// path, so existing mappings keep routing to a draining worker until they're recomputed.
+    draining: Arc<RwLock<HashSet<u32>>>,
+    /// Number of stage tasks currently in flight on each worker node, keyed by worker id.
+    /// Used by load-aware [`WorkerSelectionPolicy`] implementations such as
+    /// [`LeastLoadedPolicy`].
+    in_flight_task_count: RwLock<HashMap<u32, Arc<AtomicUsize>>>,
+    /// Command channel to the background loop started by [`Self::start_health_checker`], if
+    /// one is running. `None` until started, and reset to `None` by
+    /// [`Self::stop_health_checker`] (dropping the sender ends the loop).
+    health_checker: Mutex<Option<mpsc::UnboundedSender<HealthCheckerCommand>>>,
 }
 
+#[derive(Clone)]
 struct WorkerNodeManagerInner {
     worker_nodes: Vec<WorkerNode>,
     /// A cache for parallel units to worker nodes. It should be consistent with `worker_nodes`.
@@ -54,19 +84,23 @@ impl Default for WorkerNodeManager {
 impl WorkerNodeManager {
     pub fn new() -> Self {
         Self {
-            inner: RwLock::new(WorkerNodeManagerInner {
+            inner: ArcSwap::from_pointee(WorkerNodeManagerInner {
                 worker_nodes: Default::default(),
                 pu_to_worker: Default::default(),
                 streaming_fragment_vnode_mapping: Default::default(),
                 serving_fragment_vnode_mapping: Default::default(),
             }),
             worker_node_mask: Arc::new(Default::default()),
+            mask_metrics: WorkerMaskMetrics::instance(),
+            draining: Arc::new(Default::default()),
+            in_flight_task_count: Default::default(),
+            health_checker: Mutex::new(None),
         }
     }
 
     /// Used in tests.
     pub fn mock(worker_nodes: Vec<WorkerNode>) -> Self {
-        let inner = RwLock::new(WorkerNodeManagerInner {
+        let inner = ArcSwap::from_pointee(WorkerNodeManagerInner {
             pu_to_worker: get_pu_to_worker_mapping(&worker_nodes),
             worker_nodes,
             streaming_fragment_vnode_mapping: HashMap::new(),
@@ -75,13 +109,45 @@ impl WorkerNodeManager {
         Self {
             inner,
             worker_node_mask: Arc::new(Default::default()),
+            mask_metrics: WorkerMaskMetrics::instance(),
+            draining: Arc::new(Default::default()),
+            in_flight_task_count: Default::default(),
+            health_checker: Mutex::new(None),
         }
     }
 
-    pub fn list_worker_nodes(&self) -> Vec<WorkerNode> {
-        self.inner
+    /// Returns the current number of in-flight stage tasks dispatched to `worker_id`.
+    pub fn in_flight_task_count(&self, worker_id: u32) -> usize {
+        self.in_flight_task_count
             .read()
             .unwrap()
+            .get(&worker_id)
+            .map_or(0, |c| c.load(Ordering::Relaxed))
+    }
+
+    /// Records that a stage task has been dispatched to `worker_id`. Should be paired with a
+    /// call to [`Self::dec_in_flight_task_count`] once the task completes or fails.
+    pub fn inc_in_flight_task_count(&self, worker_id: u32) {
+        let counter = self
+            .in_flight_task_count
+            .write()
+            .unwrap()
+            .entry(worker_id)
+            .or_insert_with(|| Arc::new(AtomicUsize::new(0)))
+            .clone();
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records that an in-flight stage task on `worker_id` has finished (successfully or not).
+    pub fn dec_in_flight_task_count(&self, worker_id: u32) {
+        if let Some(counter) = self.in_flight_task_count.read().unwrap().get(&worker_id) {
+            counter.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn list_worker_nodes(&self) -> Vec<WorkerNode> {
+        self.inner
+            .load()
             .worker_nodes
             .iter()
             .filter(|w| w.r#type() == WorkerType::ComputeNode)
@@ -96,6 +162,16 @@ impl WorkerNodeManager {
             .collect()
     }
 
+    /// Total number of parallel units across currently serving worker nodes. Used to derive a
+    /// default stage parallelism when a caller has not resolved a concrete degree up front,
+    /// analogous to defaulting a job count to `std::thread::available_parallelism()`.
+    pub fn total_available_parallel_units(&self) -> usize {
+        self.list_serving_worker_nodes()
+            .iter()
+            .map(|w| w.parallel_units.len())
+            .sum()
+    }
+
     fn list_streaming_worker_nodes(&self) -> Vec<WorkerNode> {
         self.list_worker_nodes()
             .into_iter()
@@ -104,27 +180,27 @@ impl WorkerNodeManager {
     }
 
     pub fn add_worker_node(&self, node: WorkerNode) {
-        let mut write_guard = self.inner.write().unwrap();
-        // update
-        for w in &mut write_guard.worker_nodes {
-            if w.id == node.id {
-                *w = node;
-                return;
+        self.inner.rcu(|inner| {
+            let mut inner = (**inner).clone();
+            // update
+            if let Some(w) = inner.worker_nodes.iter_mut().find(|w| w.id == node.id) {
+                *w = node.clone();
+            } else {
+                // insert
+                inner.worker_nodes.push(node.clone());
             }
-        }
-        // insert
-        write_guard.worker_nodes.push(node);
-
-        // Update `pu_to_worker`
-        write_guard.pu_to_worker = get_pu_to_worker_mapping(&write_guard.worker_nodes);
+            inner.pu_to_worker = get_pu_to_worker_mapping(&inner.worker_nodes);
+            inner
+        });
     }
 
     pub fn remove_worker_node(&self, node: WorkerNode) {
-        let mut write_guard = self.inner.write().unwrap();
-        write_guard.worker_nodes.retain(|x| x.id != node.id);
-
-        // Update `pu_to_worker`
-        write_guard.pu_to_worker = get_pu_to_worker_mapping(&write_guard.worker_nodes);
+        self.inner.rcu(|inner| {
+            let mut inner = (**inner).clone();
+            inner.worker_nodes.retain(|x| x.id != node.id);
+            inner.pu_to_worker = get_pu_to_worker_mapping(&inner.worker_nodes);
+            inner
+        });
     }
 
     pub fn refresh(
@@ -133,7 +209,6 @@ impl WorkerNodeManager {
         streaming_mapping: HashMap<FragmentId, ParallelUnitMapping>,
         serving_mapping: HashMap<FragmentId, ParallelUnitMapping>,
     ) {
-        let mut write_guard = self.inner.write().unwrap();
         tracing::debug!("Refresh worker nodes {:?}.", nodes);
         tracing::debug!(
             "Refresh streaming vnode mapping for fragments {:?}.",
@@ -143,11 +218,13 @@ impl WorkerNodeManager {
             "Refresh serving vnode mapping for fragments {:?}.",
             serving_mapping.keys()
         );
-        write_guard.worker_nodes = nodes;
-        // Update `pu_to_worker`
-        write_guard.pu_to_worker = get_pu_to_worker_mapping(&write_guard.worker_nodes);
-        write_guard.streaming_fragment_vnode_mapping = streaming_mapping;
-        write_guard.serving_fragment_vnode_mapping = serving_mapping;
+        let pu_to_worker = get_pu_to_worker_mapping(&nodes);
+        self.inner.store(Arc::new(WorkerNodeManagerInner {
+            worker_nodes: nodes,
+            pu_to_worker,
+            streaming_fragment_vnode_mapping: streaming_mapping,
+            serving_fragment_vnode_mapping: serving_mapping,
+        }));
     }
 
     /// If parallel unit ids is empty, the scheduler may fail to schedule any task and stuck at
@@ -161,7 +238,7 @@ impl WorkerNodeManager {
             return Err(SchedulerError::EmptyWorkerNodes);
         }
 
-        let guard = self.inner.read().unwrap();
+        let guard = self.inner.load();
 
         let mut workers = Vec::with_capacity(parallel_unit_ids.len());
         for parallel_unit_id in parallel_unit_ids {
@@ -181,8 +258,7 @@ impl WorkerNodeManager {
         fragment_id: &FragmentId,
     ) -> SchedulerResult<ParallelUnitMapping> {
         self.inner
-            .read()
-            .unwrap()
+            .load()
             .streaming_fragment_vnode_mapping
             .get(fragment_id)
             .cloned()
@@ -194,12 +270,14 @@ impl WorkerNodeManager {
         fragment_id: FragmentId,
         vnode_mapping: ParallelUnitMapping,
     ) {
-        self.inner
-            .write()
-            .unwrap()
-            .streaming_fragment_vnode_mapping
-            .try_insert(fragment_id, vnode_mapping)
-            .unwrap();
+        self.inner.rcu(|inner| {
+            let mut inner = (**inner).clone();
+            inner
+                .streaming_fragment_vnode_mapping
+                .try_insert(fragment_id, vnode_mapping.clone())
+                .unwrap();
+            inner
+        });
     }
 
     pub fn update_streaming_fragment_mapping(
@@ -207,19 +285,25 @@ impl WorkerNodeManager {
         fragment_id: FragmentId,
         vnode_mapping: ParallelUnitMapping,
     ) {
-        let mut guard = self.inner.write().unwrap();
-        guard
-            .streaming_fragment_vnode_mapping
-            .insert(fragment_id, vnode_mapping)
-            .unwrap();
+        self.inner.rcu(|inner| {
+            let mut inner = (**inner).clone();
+            inner
+                .streaming_fragment_vnode_mapping
+                .insert(fragment_id, vnode_mapping.clone())
+                .unwrap();
+            inner
+        });
     }
 
     pub fn remove_streaming_fragment_mapping(&self, fragment_id: &FragmentId) {
-        let mut guard = self.inner.write().unwrap();
-        guard
-            .streaming_fragment_vnode_mapping
-            .remove(fragment_id)
-            .unwrap();
+        self.inner.rcu(|inner| {
+            let mut inner = (**inner).clone();
+            inner
+                .streaming_fragment_vnode_mapping
+                .remove(fragment_id)
+                .unwrap();
+            inner
+        });
     }
 
     /// Returns fragment's vnode mapping for serving.
@@ -228,67 +312,349 @@ impl WorkerNodeManager {
         fragment_id: FragmentId,
     ) -> SchedulerResult<ParallelUnitMapping> {
         self.inner
-            .read()
-            .unwrap()
+            .load()
             .get_serving_fragment_mapping(fragment_id)
             .ok_or_else(|| SchedulerError::ServingVnodeMappingNotFound(fragment_id))
     }
 
     pub fn set_serving_fragment_mapping(&self, mappings: HashMap<FragmentId, ParallelUnitMapping>) {
-        let mut guard = self.inner.write().unwrap();
         tracing::debug!(
             "Set serving vnode mapping for fragments {:?}",
             mappings.keys()
         );
-        guard.serving_fragment_vnode_mapping = mappings;
+        self.inner.rcu(|inner| {
+            let mut inner = (**inner).clone();
+            inner.serving_fragment_vnode_mapping = mappings.clone();
+            inner
+        });
     }
 
     pub fn upsert_serving_fragment_mapping(
         &self,
         mappings: HashMap<FragmentId, ParallelUnitMapping>,
     ) {
-        let mut guard = self.inner.write().unwrap();
         tracing::debug!(
             "Upsert serving vnode mapping for fragments {:?}",
             mappings.keys()
         );
-        for (fragment_id, mapping) in mappings {
-            guard
-                .serving_fragment_vnode_mapping
-                .insert(fragment_id, mapping);
-        }
+        self.inner.rcu(|inner| {
+            let mut inner = (**inner).clone();
+            for (fragment_id, mapping) in &mappings {
+                inner
+                    .serving_fragment_vnode_mapping
+                    .insert(*fragment_id, mapping.clone());
+            }
+            inner
+        });
     }
 
     pub fn remove_serving_fragment_mapping(&self, fragment_ids: &[FragmentId]) {
-        let mut guard = self.inner.write().unwrap();
         tracing::debug!(
             "Delete serving vnode mapping for fragments {:?}",
             fragment_ids
         );
-        for fragment_id in fragment_ids {
-            guard.serving_fragment_vnode_mapping.remove(fragment_id);
-        }
+        self.inner.rcu(|inner| {
+            let mut inner = (**inner).clone();
+            for fragment_id in fragment_ids {
+                inner.serving_fragment_vnode_mapping.remove(fragment_id);
+            }
+            inner
+        });
     }
 
-    fn worker_node_mask(&self) -> RwLockReadGuard<'_, HashSet<u32>> {
+    fn worker_node_mask(&self) -> RwLockReadGuard<'_, HashMap<u32, MaskedWorkerInfo>> {
         self.worker_node_mask.read().unwrap()
     }
 
+    /// Returns structured info about every currently masked worker, for admin/metrics
+    /// introspection (e.g. to show *why* and *since when* a worker has been excluded from
+    /// query scheduling, not just that it has).
+    pub fn list_masked_workers(&self) -> Vec<MaskedWorkerInfo> {
+        self.worker_node_mask().values().cloned().collect()
+    }
+
+    fn draining(&self) -> RwLockReadGuard<'_, HashSet<u32>> {
+        self.draining.read().unwrap()
+    }
+
+    /// Marks `worker_node_id` as gracefully draining: an operator-initiated, persistent
+    /// exclusion (no auto-expiry, unlike [`Self::mask_worker_node`]) from newly computed serving
+    /// placements, while existing cached fragment mappings keep routing to it until refreshed.
+    /// Intended for planned scale-in, where the operator -- not a failure -- decides when the
+    /// worker should stop taking on new work.
+    pub fn drain_worker_node(&self, worker_node_id: u32) {
+        self.draining.write().unwrap().insert(worker_node_id);
+    }
+
+    /// Reverses [`Self::drain_worker_node`].
+    pub fn undrain_worker_node(&self, worker_node_id: u32) {
+        self.draining.write().unwrap().remove(&worker_node_id);
+    }
+
     pub fn mask_worker_node(&self, worker_node_id: u32, duration: Duration) {
+        self.mask_worker_node_with_reason(worker_node_id, duration, "unspecified");
+    }
+
+    /// Same as [`Self::mask_worker_node`], but records `reason` so it shows up in
+    /// [`Self::list_masked_workers`]. If `worker_node_id` is already masked, its reason and
+    /// `fail_count` are updated but its existing expiry is left alone.
+    pub fn mask_worker_node_with_reason(
+        &self,
+        worker_node_id: u32,
+        duration: Duration,
+        reason: impl Into<String>,
+    ) {
         let mut worker_node_mask = self.worker_node_mask.write().unwrap();
-        if worker_node_mask.contains(&worker_node_id) {
+        if let Some(info) = worker_node_mask.get_mut(&worker_node_id) {
+            info.reason = reason.into();
+            info.fail_count += 1;
             return;
         }
-        worker_node_mask.insert(worker_node_id);
+        let now = Instant::now();
+        worker_node_mask.insert(
+            worker_node_id,
+            MaskedWorkerInfo {
+                worker_id: worker_node_id,
+                masked_since: now,
+                expires_at: now + duration,
+                reason: reason.into(),
+                fail_count: 1,
+            },
+        );
+        drop(worker_node_mask);
+        self.mask_metrics.masked_count.inc();
+        self.mask_metrics.mask_events_total.inc();
         let worker_node_mask_ref = self.worker_node_mask.clone();
+        let mask_metrics = self.mask_metrics.clone();
         tokio::spawn(async move {
             tokio::time::sleep(duration).await;
-            worker_node_mask_ref
+            if worker_node_mask_ref
                 .write()
                 .unwrap()
-                .remove(&worker_node_id);
+                .remove(&worker_node_id)
+                .is_some()
+            {
+                mask_metrics.masked_count.dec();
+            }
+        });
+    }
+
+    /// Starts the background loop described by [`HealthCheckerConfig`]: periodically calls
+    /// `probe` for every known compute node, and on repeated failures masks it via
+    /// [`Self::mask_worker_node`] with an exponentially growing duration, resetting on the next
+    /// successful probe. Replaces any previously running loop (dropping its command sender stops
+    /// it).
+    ///
+    /// `probe` stands in for the lightweight RPC ping described in the design this implements --
+    /// this snapshot doesn't include a meta/compute RPC client to issue a real one, so callers
+    /// inject whatever check is appropriate for their environment.
+    pub fn start_health_checker<F, Fut>(self: &Arc<Self>, config: HealthCheckerConfig, probe: F)
+    where
+        F: Fn(WorkerNode) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+        *self.health_checker.lock().unwrap() = Some(command_tx);
+        let manager = self.clone();
+        tokio::spawn(async move {
+            WorkerHealthChecker::run(manager, config, probe, command_rx).await;
         });
     }
+
+    /// Stops the background health-probing loop started by [`Self::start_health_checker`], if
+    /// any is running.
+    pub fn stop_health_checker(&self) {
+        self.health_checker.lock().unwrap().take();
+    }
+
+    /// Pauses probing without stopping the loop; in-flight backoff state for each worker is
+    /// preserved and probing resumes where it left off on [`Self::resume_health_checker`].
+    pub fn pause_health_checker(&self) {
+        self.send_health_checker_command(HealthCheckerCommand::Pause);
+    }
+
+    /// Resumes a loop previously paused with [`Self::pause_health_checker`].
+    pub fn resume_health_checker(&self) {
+        self.send_health_checker_command(HealthCheckerCommand::Resume);
+    }
+
+    /// Requests an immediate out-of-band probe of `worker_id`, bypassing its current backoff
+    /// schedule. Useful right after an operator believes a flapping node has recovered.
+    pub fn force_probe(&self, worker_id: u32) {
+        self.send_health_checker_command(HealthCheckerCommand::ForceProbe(worker_id));
+    }
+
+    fn send_health_checker_command(&self, command: HealthCheckerCommand) {
+        if let Some(tx) = self.health_checker.lock().unwrap().as_ref() {
+            // The receiver only goes away when the loop has already stopped, in which case
+            // there's nothing to signal.
+            let _ = tx.send(command);
+        }
+    }
+}
+
+/// Structured info about a worker currently masked out of serving query scheduling, returned by
+/// [`WorkerNodeManager::list_masked_workers`].
+#[derive(Debug, Clone)]
+pub struct MaskedWorkerInfo {
+    pub worker_id: u32,
+    pub masked_since: Instant,
+    pub expires_at: Instant,
+    pub reason: String,
+    pub fail_count: u32,
+}
+
+/// Masked-worker-count gauge and total-mask-events counter, registered once against the global
+/// default [`prometheus::Registry`] via [`Self::instance`] regardless of how many
+/// `WorkerNodeManager`s exist in the process (re-registering the same metric name twice panics).
+struct WorkerMaskMetrics {
+    masked_count: IntGauge,
+    mask_events_total: IntCounter,
+}
+
+impl WorkerMaskMetrics {
+    fn instance() -> Arc<WorkerMaskMetrics> {
+        static INSTANCE: OnceLock<Arc<WorkerMaskMetrics>> = OnceLock::new();
+        INSTANCE
+            .get_or_init(|| {
+                Arc::new(WorkerMaskMetrics {
+                    masked_count: register_int_gauge!(
+                        "frontend_worker_node_masked_count",
+                        "number of worker nodes currently masked out of serving query scheduling"
+                    )
+                    .unwrap(),
+                    mask_events_total: register_int_counter!(
+                        "frontend_worker_node_mask_events_total",
+                        "total number of times a worker node has been masked"
+                    )
+                    .unwrap(),
+                })
+            })
+            .clone()
+    }
+}
+
+/// Tuning knobs for [`WorkerNodeManager::start_health_checker`].
+#[derive(Debug, Clone, Copy)]
+pub struct HealthCheckerConfig {
+    /// How often each worker is probed while healthy.
+    pub probe_interval: Duration,
+    /// Mask duration applied after the first consecutive failure; doubles with each further
+    /// consecutive failure until it reaches `max_backoff`.
+    pub base_backoff: Duration,
+    /// Upper bound on the mask duration, regardless of how many consecutive failures a worker
+    /// has accumulated.
+    pub max_backoff: Duration,
+}
+
+enum HealthCheckerCommand {
+    Pause,
+    Resume,
+    ForceProbe(u32),
+}
+
+/// Per-worker state tracked by [`WorkerHealthChecker`] across probes.
+#[derive(Default)]
+struct WorkerHealthState {
+    consecutive_failures: u32,
+    last_probe: Option<Instant>,
+    next_retry: Option<Instant>,
+}
+
+/// The background loop behind [`WorkerNodeManager::start_health_checker`]. This is a single
+/// cancelable task rather than a detached `tokio::spawn` per mask, so pausing, resuming, and
+/// forcing a probe are just messages on `command_rx` instead of requiring a new task each time.
+struct WorkerHealthChecker;
+
+impl WorkerHealthChecker {
+    async fn run<F, Fut>(
+        manager: Arc<WorkerNodeManager>,
+        config: HealthCheckerConfig,
+        probe: F,
+        mut command_rx: mpsc::UnboundedReceiver<HealthCheckerCommand>,
+    ) where
+        F: Fn(WorkerNode) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        let mut paused = false;
+        let mut states: HashMap<u32, WorkerHealthState> = HashMap::new();
+        let mut tick = tokio::time::interval(config.probe_interval);
+        loop {
+            tokio::select! {
+                _ = tick.tick() => {
+                    if paused {
+                        continue;
+                    }
+                    let now = Instant::now();
+                    for worker in manager.list_worker_nodes() {
+                        let state = states.entry(worker.id).or_default();
+                        if state.next_retry.map_or(false, |next_retry| now < next_retry) {
+                            continue;
+                        }
+                        Self::probe_one(&manager, &probe, &config, worker, state, now).await;
+                    }
+                }
+                command = command_rx.recv() => {
+                    match command {
+                        Some(HealthCheckerCommand::Pause) => paused = true,
+                        Some(HealthCheckerCommand::Resume) => paused = false,
+                        Some(HealthCheckerCommand::ForceProbe(worker_id)) => {
+                            if let Some(worker) = manager
+                                .list_worker_nodes()
+                                .into_iter()
+                                .find(|w| w.id == worker_id)
+                            {
+                                let now = Instant::now();
+                                let state = states.entry(worker_id).or_default();
+                                Self::probe_one(&manager, &probe, &config, worker, state, now).await;
+                            }
+                        }
+                        // The manager dropped (or replaced) our sender; shut down.
+                        None => break,
+                    }
+                }
+            }
+        }
+    }
+
+    async fn probe_one<F, Fut>(
+        manager: &Arc<WorkerNodeManager>,
+        probe: &F,
+        config: &HealthCheckerConfig,
+        worker: WorkerNode,
+        state: &mut WorkerHealthState,
+        now: Instant,
+    ) where
+        F: Fn(WorkerNode) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        state.last_probe = Some(now);
+        let worker_id = worker.id;
+        let healthy = probe(worker).await;
+        if healthy {
+            state.consecutive_failures = 0;
+            state.next_retry = Some(now + config.probe_interval);
+        } else {
+            let exp = 1u32
+                .checked_shl(state.consecutive_failures)
+                .unwrap_or(u32::MAX);
+            let backoff = config
+                .base_backoff
+                .checked_mul(exp)
+                .unwrap_or(config.max_backoff)
+                .min(config.max_backoff);
+            state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+            state.next_retry = Some(now + backoff);
+            manager.mask_worker_node_with_reason(
+                worker_id,
+                backoff,
+                format!(
+                    "health probe failed ({} consecutive failures)",
+                    state.consecutive_failures
+                ),
+            );
+        }
+    }
 }
 
 impl WorkerNodeManagerInner {
@@ -299,11 +665,342 @@ impl WorkerNodeManagerInner {
     }
 }
 
+/// Computes a vnode -> worker assignment that distributes `vnode_count` vnodes across `workers`
+/// in proportion to each worker's parallel unit count, while reusing as much of
+/// `current_assignment` as possible, i.e. minimizing the number of vnodes that move to a
+/// different worker when cluster membership changes.
+///
+/// This is a min-cost max-flow problem: source -> vnode (capacity 1, cost 0), vnode -> eligible
+/// worker (capacity 1, cost 0 if `current_assignment` already had that vnode on that worker, else
+/// cost 1), worker -> sink (capacity `target_i`, cost 0), solved by successive shortest augmenting
+/// paths. `target_i` is `floor(vnode_count * weight_i / total_weight)`, with the rounding
+/// remainder distributed to the largest-weight workers first (ties broken by worker id) so the
+/// targets sum to exactly `vnode_count`. Bellman-Ford is used to find each shortest path rather
+/// than Dijkstra with Johnson potentials, since costs are only ever 0 or 1 and the network is
+/// small, so the simpler algorithm is fast enough.
+///
+/// Returns `None` if `workers` is empty, every worker has zero weight, or not every vnode can be
+/// covered.
+///
+/// Note: this is the placement algorithm itself, written so that it could be called from
+/// `place_vnode`'s fallback path and from serving-mapping recomputation. Actually wiring it in
+/// (taking `hint: Option<&ParallelUnitMapping>` / returning `ParallelUnitMapping` the way
+/// `place_vnode` does, keyed by parallel unit id rather than worker id) needs
+/// `risingwave_common::vnode_mapping::vnode_placement`'s and `ParallelUnitMapping`'s actual
+/// construction APIs, neither of which is part of this snapshot.
+fn min_cost_flow_vnode_placement(
+    current_assignment: &HashMap<usize, u32>,
+    workers: &[(u32, usize)],
+    vnode_count: usize,
+) -> Option<HashMap<usize, u32>> {
+    if vnode_count == 0 || workers.is_empty() || workers.iter().all(|&(_, weight)| weight == 0) {
+        return None;
+    }
+
+    let mut workers = workers.to_vec();
+    workers.sort_by_key(|&(id, _)| id);
+
+    let total_weight: usize = workers.iter().map(|&(_, weight)| weight).sum();
+    let mut targets: Vec<usize> = workers
+        .iter()
+        .map(|&(_, weight)| vnode_count * weight / total_weight)
+        .collect();
+    let mut remainder = vnode_count - targets.iter().sum::<usize>();
+    let mut by_weight_desc: Vec<usize> = (0..workers.len()).collect();
+    by_weight_desc.sort_by(|&a, &b| {
+        workers[b]
+            .1
+            .cmp(&workers[a].1)
+            .then(workers[a].0.cmp(&workers[b].0))
+    });
+    for &idx in &by_weight_desc {
+        if remainder == 0 {
+            break;
+        }
+        targets[idx] += 1;
+        remainder -= 1;
+    }
+
+    let num_workers = workers.len();
+    let source = 0usize;
+    let vnode_base = 1usize;
+    let worker_base = vnode_base + vnode_count;
+    let sink = worker_base + num_workers;
+    let num_nodes = sink + 1;
+
+    struct Edge {
+        to: usize,
+        cap: i64,
+        cost: i64,
+    }
+    let mut edges: Vec<Edge> = Vec::new();
+    let mut graph: Vec<Vec<usize>> = vec![Vec::new(); num_nodes];
+    let mut add_edge = |graph: &mut Vec<Vec<usize>>,
+                        edges: &mut Vec<Edge>,
+                        from: usize,
+                        to: usize,
+                        cap: i64,
+                        cost: i64| {
+        graph[from].push(edges.len());
+        edges.push(Edge { to, cap, cost });
+        graph[to].push(edges.len());
+        edges.push(Edge {
+            to: from,
+            cap: 0,
+            cost: -cost,
+        });
+    };
+
+    for v in 0..vnode_count {
+        add_edge(&mut graph, &mut edges, source, vnode_base + v, 1, 0);
+    }
+    for (w_idx, &(worker_id, _)) in workers.iter().enumerate() {
+        for v in 0..vnode_count {
+            let cost = if current_assignment.get(&v) == Some(&worker_id) {
+                0
+            } else {
+                1
+            };
+            add_edge(
+                &mut graph,
+                &mut edges,
+                vnode_base + v,
+                worker_base + w_idx,
+                1,
+                cost,
+            );
+        }
+        add_edge(
+            &mut graph,
+            &mut edges,
+            worker_base + w_idx,
+            sink,
+            targets[w_idx] as i64,
+            0,
+        );
+    }
+
+    // Successive shortest augmenting paths, each found via Bellman-Ford (SPFA) over the residual
+    // graph so that negative-cost residual edges are handled correctly.
+    loop {
+        let mut dist = vec![i64::MAX; num_nodes];
+        let mut in_queue = vec![false; num_nodes];
+        let mut prev_edge = vec![usize::MAX; num_nodes];
+        dist[source] = 0;
+        let mut queue = std::collections::VecDeque::new();
+        queue.push_back(source);
+        in_queue[source] = true;
+        while let Some(u) = queue.pop_front() {
+            in_queue[u] = false;
+            for &e_idx in &graph[u] {
+                let e = &edges[e_idx];
+                if e.cap > 0 && dist[u] != i64::MAX && dist[u] + e.cost < dist[e.to] {
+                    dist[e.to] = dist[u] + e.cost;
+                    prev_edge[e.to] = e_idx;
+                    if !in_queue[e.to] {
+                        queue.push_back(e.to);
+                        in_queue[e.to] = true;
+                    }
+                }
+            }
+        }
+        if dist[sink] == i64::MAX {
+            break;
+        }
+        let mut push = i64::MAX;
+        let mut node = sink;
+        while node != source {
+            let e_idx = prev_edge[node];
+            push = push.min(edges[e_idx].cap);
+            node = edges[e_idx ^ 1].to;
+        }
+        let mut node = sink;
+        while node != source {
+            let e_idx = prev_edge[node];
+            edges[e_idx].cap -= push;
+            edges[e_idx ^ 1].cap += push;
+            node = edges[e_idx ^ 1].to;
+        }
+    }
+
+    let mut result = HashMap::with_capacity(vnode_count);
+    for v in 0..vnode_count {
+        let vnode_node = vnode_base + v;
+        for &e_idx in &graph[vnode_node] {
+            let e = &edges[e_idx];
+            if e.to >= worker_base && e.to < sink && e.cap == 0 {
+                let w_idx = e.to - worker_base;
+                result.insert(v, workers[w_idx].0);
+                break;
+            }
+        }
+    }
+
+    if result.len() != vnode_count {
+        return None;
+    }
+    Some(result)
+}
+
+/// A pluggable strategy for choosing which worker node a stage's task should be placed on.
+///
+/// `WorkerNodeSelector` used to hard-code uniform random placement. Implementing this trait
+/// lets the session/config swap in a different placement strategy without touching the
+/// scheduler itself.
+pub trait WorkerSelectionPolicy: Send + Sync {
+    /// Picks one worker node out of `candidates` to run (a task of) `stage` on. `candidates`
+    /// has already been filtered by the caller (e.g. by parallel unit constraints and the
+    /// worker node mask), so implementations only need to pick among them. `manager` is
+    /// provided so load-aware policies can consult cluster-wide state such as in-flight task
+    /// counters.
+    fn select<'a>(
+        &self,
+        stage: &QueryStage,
+        candidates: &'a [WorkerNode],
+        manager: &WorkerNodeManager,
+    ) -> SchedulerResult<&'a WorkerNode>;
+}
+
+/// Uniform random placement. This is the long-standing default behavior.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomPolicy;
+
+impl WorkerSelectionPolicy for RandomPolicy {
+    fn select<'a>(
+        &self,
+        _stage: &QueryStage,
+        candidates: &'a [WorkerNode],
+        _manager: &WorkerNodeManager,
+    ) -> SchedulerResult<&'a WorkerNode> {
+        candidates
+            .choose(&mut rand::thread_rng())
+            .ok_or(SchedulerError::EmptyWorkerNodes)
+    }
+}
+
+/// Round-robin placement: cycles through the candidate set in the order it is presented.
+#[derive(Debug, Default)]
+pub struct RoundRobinPolicy {
+    next: AtomicUsize,
+}
+
+impl WorkerSelectionPolicy for RoundRobinPolicy {
+    fn select<'a>(
+        &self,
+        _stage: &QueryStage,
+        candidates: &'a [WorkerNode],
+        _manager: &WorkerNodeManager,
+    ) -> SchedulerResult<&'a WorkerNode> {
+        if candidates.is_empty() {
+            return Err(SchedulerError::EmptyWorkerNodes);
+        }
+        let idx = self.next.fetch_add(1, Ordering::Relaxed) % candidates.len();
+        Ok(&candidates[idx])
+    }
+}
+
+/// Load-aware placement backed by [`WorkerNodeManager::in_flight_task_count`].
+///
+/// To avoid scanning every candidate under high fan-out, this uses the "power of two choices"
+/// heuristic: sample two candidates at random and keep the less-loaded one. This keeps
+/// selection `O(1)` while still avoiding the herding that uniform random placement can cause.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LeastLoadedPolicy;
+
+impl WorkerSelectionPolicy for LeastLoadedPolicy {
+    fn select<'a>(
+        &self,
+        _stage: &QueryStage,
+        candidates: &'a [WorkerNode],
+        manager: &WorkerNodeManager,
+    ) -> SchedulerResult<&'a WorkerNode> {
+        if candidates.is_empty() {
+            return Err(SchedulerError::EmptyWorkerNodes);
+        }
+        if candidates.len() == 1 {
+            return Ok(&candidates[0]);
+        }
+        let mut rng = rand::thread_rng();
+        let (a, b) = loop {
+            let a = candidates.choose(&mut rng).unwrap();
+            let b = candidates.choose(&mut rng).unwrap();
+            if a.id != b.id {
+                break (a, b);
+            }
+        };
+        let load_a = manager.in_flight_task_count(a.id);
+        let load_b = manager.in_flight_task_count(b.id);
+        Ok(if load_a <= load_b { a } else { b })
+    }
+}
+
+/// Weighted random placement where each candidate's probability of being chosen is
+/// proportional to its parallel unit count, so larger (higher-capacity) nodes receive a
+/// correspondingly larger share of placements instead of being treated the same as small ones.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WeightedRandomPolicy;
+
+impl WorkerSelectionPolicy for WeightedRandomPolicy {
+    fn select<'a>(
+        &self,
+        _stage: &QueryStage,
+        candidates: &'a [WorkerNode],
+        _manager: &WorkerNodeManager,
+    ) -> SchedulerResult<&'a WorkerNode> {
+        if candidates.is_empty() {
+            return Err(SchedulerError::EmptyWorkerNodes);
+        }
+        // Every candidate gets at least weight 1 so that nodes without parallel unit info
+        // (e.g. in tests) are still selectable.
+        let mut cum = Vec::with_capacity(candidates.len());
+        let mut total = 0u64;
+        for w in candidates {
+            total += w.parallel_units.len().max(1) as u64;
+            cum.push(total);
+        }
+        let r = rand::Rng::gen_range(&mut rand::thread_rng(), 0..total);
+        let idx = cum.partition_point(|&w| w <= r);
+        Ok(&candidates[idx])
+    }
+}
+
+/// Consistent-hash placement keyed by the stage id, so repeated scheduling of the same stage
+/// tends to land on the same worker even as the candidate set changes slightly.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsistentHashPolicy;
+
+impl WorkerSelectionPolicy for ConsistentHashPolicy {
+    fn select<'a>(
+        &self,
+        stage: &QueryStage,
+        candidates: &'a [WorkerNode],
+        _manager: &WorkerNodeManager,
+    ) -> SchedulerResult<&'a WorkerNode> {
+        if candidates.is_empty() {
+            return Err(SchedulerError::EmptyWorkerNodes);
+        }
+        fn ring_point<T: std::hash::Hash>(v: &T) -> u64 {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            std::hash::Hash::hash(v, &mut hasher);
+            std::hash::Hasher::finish(&hasher)
+        }
+        // Hash the stage id and every candidate's worker id onto the same ring, and pick the
+        // first worker at or after the stage's point, wrapping around past the end.
+        let point = ring_point(&stage.id);
+        let mut ring: Vec<(u64, &WorkerNode)> =
+            candidates.iter().map(|w| (ring_point(&w.id), w)).collect();
+        ring.sort_by_key(|&(h, w)| (h, w.id));
+        let idx = ring.partition_point(|&(h, _)| h < point) % ring.len();
+        Ok(ring[idx].1)
+    }
+}
+
 /// Selects workers for query according to `enable_barrier_read`
 #[derive(Clone)]
 pub struct WorkerNodeSelector {
     pub manager: WorkerNodeManagerRef,
     enable_barrier_read: bool,
+    selection_policy: Arc<dyn WorkerSelectionPolicy>,
 }
 
 impl WorkerNodeSelector {
@@ -311,14 +1008,22 @@ impl WorkerNodeSelector {
         Self {
             manager,
             enable_barrier_read,
+            selection_policy: Arc::new(RandomPolicy),
         }
     }
 
+    /// Returns a copy of this selector configured to use `policy` for worker placement instead
+    /// of the default uniform random policy. Intended to be driven by a per-session config.
+    pub fn with_policy(mut self, policy: Arc<dyn WorkerSelectionPolicy>) -> Self {
+        self.selection_policy = policy;
+        self
+    }
+
     pub fn worker_node_count(&self) -> usize {
         if self.enable_barrier_read {
             self.manager.list_streaming_worker_nodes().len()
         } else {
-            self.apply_worker_node_mask(self.manager.list_serving_worker_nodes())
+            self.filter_available_workers(self.manager.list_serving_worker_nodes())
                 .len()
         }
     }
@@ -327,7 +1032,7 @@ impl WorkerNodeSelector {
         let worker_nodes = if self.enable_barrier_read {
             self.manager.list_streaming_worker_nodes()
         } else {
-            self.apply_worker_node_mask(self.manager.list_serving_worker_nodes())
+            self.filter_available_workers(self.manager.list_serving_worker_nodes())
         };
         worker_nodes
             .iter()
@@ -367,7 +1072,8 @@ impl WorkerNodeSelector {
                 }
             };
             // 2. Temporary mapping that filters out unavailable workers.
-            let new_workers = self.apply_worker_node_mask(self.manager.list_serving_worker_nodes());
+            let new_workers =
+                self.filter_available_workers(self.manager.list_serving_worker_nodes());
             let masked_mapping = place_vnode(hint.as_ref(), &new_workers, parallelism);
             masked_mapping.ok_or_else(|| SchedulerError::EmptyWorkerNodes)
         }
@@ -377,7 +1083,7 @@ impl WorkerNodeSelector {
         let worker_nodes = if self.enable_barrier_read {
             self.manager.list_streaming_worker_nodes()
         } else {
-            self.apply_worker_node_mask(self.manager.list_serving_worker_nodes())
+            self.filter_available_workers(self.manager.list_serving_worker_nodes())
         };
         worker_nodes
             .choose(&mut rand::thread_rng())
@@ -385,15 +1091,32 @@ impl WorkerNodeSelector {
             .map(|w| (*w).clone())
     }
 
-    fn apply_worker_node_mask(&self, origin: Vec<WorkerNode>) -> Vec<WorkerNode> {
+    /// Selects a worker node to run (a task of) `stage` on, delegating to the configured
+    /// [`WorkerSelectionPolicy`] instead of always picking uniformly at random.
+    pub fn select_worker(&self, stage: &QueryStage) -> SchedulerResult<WorkerNode> {
+        let worker_nodes = if self.enable_barrier_read {
+            self.manager.list_streaming_worker_nodes()
+        } else {
+            self.filter_available_workers(self.manager.list_serving_worker_nodes())
+        };
+        self.selection_policy
+            .select(stage, &worker_nodes, &self.manager)
+            .map(|w| w.clone())
+    }
+
+    /// Filters `origin` down to workers that are neither masked nor draining, i.e. the
+    /// candidates a *newly computed* placement is allowed to use. Cached placements (e.g. the
+    /// fast path in `fragment_mapping`) intentionally don't go through this.
+    fn filter_available_workers(&self, origin: Vec<WorkerNode>) -> Vec<WorkerNode> {
         let mask = self.manager.worker_node_mask();
-        if origin.iter().all(|w| mask.contains(&w.id)) {
+        let draining = self.manager.draining();
+        let unavailable = |w: &WorkerNode| mask.contains_key(&w.id) || draining.contains(&w.id);
+        if origin.iter().all(unavailable) {
+            // Filtering would exclude every candidate; fail open rather than leave nothing to
+            // schedule on.
             return origin;
         }
-        origin
-            .into_iter()
-            .filter(|w| !mask.contains(&w.id))
-            .collect()
+        origin.into_iter().filter(|w| !unavailable(w)).collect()
     }
 }
 
@@ -458,4 +1181,38 @@ mod tests {
             worker_nodes.as_slice()[1..].to_vec()
         );
     }
+
+    #[test]
+    fn test_min_cost_flow_vnode_placement() {
+        use super::*;
+
+        // Evenly split 10 vnodes across two equally-weighted workers.
+        let assignment =
+            min_cost_flow_vnode_placement(&HashMap::new(), &[(1, 1), (2, 1)], 10).unwrap();
+        assert_eq!(assignment.len(), 10);
+        let mut counts = HashMap::new();
+        for worker_id in assignment.values() {
+            *counts.entry(*worker_id).or_insert(0) += 1;
+        }
+        assert_eq!(counts[&1], 5);
+        assert_eq!(counts[&2], 5);
+
+        // Adding a third, equally-weighted worker should only move vnodes off the existing two
+        // workers to fill the new one, never move a vnode between the two existing workers.
+        let rebalanced =
+            min_cost_flow_vnode_placement(&assignment, &[(1, 1), (2, 1), (3, 1)], 10).unwrap();
+        assert_eq!(rebalanced.len(), 10);
+        let moved_between_old_workers = assignment.iter().any(|(vnode, &old_worker)| {
+            let new_worker = rebalanced[vnode];
+            new_worker != old_worker
+                && old_worker != 3
+                && new_worker != 3
+                && new_worker != old_worker
+        });
+        assert!(!moved_between_old_workers);
+
+        // No workers with positive weight: can't place anything.
+        assert!(min_cost_flow_vnode_placement(&HashMap::new(), &[], 10).is_none());
+        assert!(min_cost_flow_vnode_placement(&HashMap::new(), &[(1, 0)], 10).is_none());
+    }
 }
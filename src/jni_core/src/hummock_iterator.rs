@@ -15,7 +15,8 @@
 use std::sync::Arc;
 
 use bytes::Bytes;
-use futures::TryStreamExt;
+use futures::stream::BoxStream;
+use futures::{stream, StreamExt, TryStreamExt};
 use risingwave_common::catalog::ColumnDesc;
 use risingwave_common::config::ObjectStoreConfig;
 use risingwave_common::hash::VirtualNode;
@@ -23,7 +24,9 @@ use risingwave_common::row::OwnedRow;
 use risingwave_common::util::select_all;
 use risingwave_common::util::value_encoding::column_aware_row_encoding::ColumnAwareSerde;
 use risingwave_common::util::value_encoding::{BasicSerde, EitherSerde, ValueRowDeserializer};
-use risingwave_hummock_sdk::key::{map_table_key_range, prefixed_range_with_vnode, TableKeyRange};
+use risingwave_hummock_sdk::key::{
+    map_table_key_range, prefixed_range_with_vnode, FullKey, TableKeyRange,
+};
 use risingwave_object_store::object::build_remote_object_store;
 use risingwave_object_store::object::object_metrics::ObjectStoreMetrics;
 use risingwave_pb::java_binding::key_range::Bound;
@@ -35,24 +38,53 @@ use risingwave_storage::hummock::store::HummockStorageIterator;
 use risingwave_storage::hummock::{CachePolicy, FileCache, SstableStore};
 use risingwave_storage::monitor::HummockStateStoreMetrics;
 use risingwave_storage::row_serde::value_serde::ValueRowSerdeNew;
-use risingwave_storage::store::{ReadOptions, StateStoreReadIterStream, StreamTypeOfIter};
+use risingwave_storage::store::{ReadOptions, StateStoreIterItem, StreamTypeOfIter};
 use tokio::sync::mpsc::unbounded_channel;
 
-type SelectAllIterStream = impl StateStoreReadIterStream + Unpin;
+type SelectAllIterStream = BoxStream<'static, StorageResult<StateStoreIterItem>>;
 
-fn select_all_vnode_stream(
+/// Merges the per-vnode streams. When `reverse` is requested, each vnode's (forward-ordered)
+/// stream is drained into a `Vec` and replayed back-to-front before merging, since the
+/// `HummockVersionReader` this binding reads through doesn't expose a native backward iterator in
+/// this snapshot; this trades memory for correctness rather than leaving `reverse` unsupported.
+async fn select_all_vnode_stream(
     streams: Vec<StreamTypeOfIter<HummockStorageIterator>>,
-) -> SelectAllIterStream {
-    select_all(streams.into_iter().map(Box::pin))
+    reverse: bool,
+) -> StorageResult<SelectAllIterStream> {
+    if !reverse {
+        return Ok(select_all(streams.into_iter().map(Box::pin)).boxed());
+    }
+    let mut reversed_streams = Vec::with_capacity(streams.len());
+    for s in streams {
+        let mut rows = s.try_collect::<Vec<_>>().await?;
+        rows.reverse();
+        reversed_streams.push(stream::iter(rows.into_iter().map(Ok)).boxed());
+    }
+    Ok(select_all(reversed_streams).boxed())
 }
 
 pub struct HummockJavaBindingIterator {
     row_serde: EitherSerde,
     stream: SelectAllIterStream,
+    /// Column indices to project rows down to after decoding, only set for `BasicSerde` tables:
+    /// `ColumnAwareSerde` is already constructed against `projected_columns`, so it never
+    /// materializes the non-selected columns in the first place. `None` means no projection is
+    /// needed (all columns were requested).
+    post_projection: Option<Vec<usize>>,
 }
 
 impl HummockJavaBindingIterator {
-    pub async fn new(read_plan: ReadPlan) -> StorageResult<Self> {
+    /// `reverse` requests that each vnode's rows be yielded in descending key order (rows of
+    /// different vnodes are interleaved, as in the forward case), and `projected_columns`
+    /// restricts the yielded rows to those column indices (empty means all columns). Both are
+    /// taken as explicit arguments rather than `ReadPlan` fields because the `.proto` source that
+    /// would add such fields to `ReadPlan` isn't part of this snapshot; callers should thread them
+    /// through from their own scan options once those fields exist.
+    pub async fn new(
+        read_plan: ReadPlan,
+        reverse: bool,
+        projected_columns: Vec<usize>,
+    ) -> StorageResult<Self> {
         // Note(bugen): should we forward the implementation to the `StorageTable`?
         let object_store = Arc::new(
             build_remote_object_store(
@@ -103,42 +135,87 @@ impl HummockJavaBindingIterator {
             streams.push(stream);
         }
 
-        let stream = select_all_vnode_stream(streams);
+        let stream = select_all_vnode_stream(streams, reverse).await?;
 
         let table = read_plan.table_catalog.unwrap();
         let versioned = table.version.is_some();
-        let table_columns = table
+        let table_columns: Vec<ColumnDesc> = table
             .columns
             .into_iter()
-            .map(|c| ColumnDesc::from(c.column_desc.unwrap()));
+            .map(|c| ColumnDesc::from(c.column_desc.unwrap()))
+            .collect();
+        let column_count = table_columns.len();
+
+        let projection: Vec<usize> = if projected_columns.is_empty() {
+            (0..column_count).collect()
+        } else {
+            projected_columns
+        };
+        let is_full_projection = projection.len() == column_count;
 
         // Decide which serializer to use based on whether the table is versioned or not.
-        let row_serde = if versioned {
-            ColumnAwareSerde::new(
-                Arc::from_iter(0..table_columns.len()),
-                Arc::from_iter(table_columns),
-            )
-            .into()
+        let (row_serde, post_projection): (EitherSerde, Option<Vec<usize>>) = if versioned {
+            // `ColumnAwareSerde` decodes directly against the given column indices, so
+            // non-selected column chunks are never materialized.
+            let serde =
+                ColumnAwareSerde::new(Arc::from_iter(projection), Arc::from_iter(table_columns));
+            (serde.into(), None)
         } else {
-            BasicSerde::new(
-                Arc::from_iter(0..table_columns.len()),
+            // `BasicSerde`'s positional encoding has no per-column skip path, so it always
+            // decodes the full row; project it down to the requested columns afterwards.
+            let serde = BasicSerde::new(
+                Arc::from_iter(0..column_count),
                 Arc::from_iter(table_columns),
-            )
-            .into()
+            );
+            (serde.into(), (!is_full_projection).then_some(projection))
         };
 
-        Ok(Self { row_serde, stream })
+        Ok(Self {
+            row_serde,
+            stream,
+            post_projection,
+        })
     }
 
     pub async fn next(&mut self) -> StorageResult<Option<(Bytes, OwnedRow)>> {
-        let item = self.stream.try_next().await?;
-        Ok(match item {
-            Some((key, value)) => Some((
-                key.user_key.table_key.0,
-                OwnedRow::new(self.row_serde.deserialize(&value)?),
-            )),
-            None => None,
-        })
+        match self.stream.try_next().await? {
+            Some((key, value)) => self.decode_row(key, value),
+            None => Ok(None),
+        }
+    }
+
+    /// Non-blocking counterpart of [`Self::next`], for hosts (e.g. a foreign JNI event loop) that
+    /// multiplex many iterators on one thread instead of blocking a runtime thread per iterator.
+    /// Drives the underlying SST/block I/O exactly as `next` does, but returns
+    /// [`Poll::Pending`] instead of awaiting when the next row isn't ready yet; the caller's
+    /// `cx.waker()` is registered with whatever I/O the stream is waiting on (e.g. an SST block
+    /// fetch) and is woken once it completes, the same way any other `Stream`/`Future` poll loop
+    /// works -- there's no separate "readiness handle" to register beyond the waker itself.
+    pub fn poll_next(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<StorageResult<Option<(Bytes, OwnedRow)>>> {
+        match self.stream.poll_next_unpin(cx) {
+            std::task::Poll::Ready(Some(Ok((key, value)))) => {
+                std::task::Poll::Ready(self.decode_row(key, value))
+            }
+            std::task::Poll::Ready(Some(Err(e))) => std::task::Poll::Ready(Err(e)),
+            std::task::Poll::Ready(None) => std::task::Poll::Ready(Ok(None)),
+            std::task::Poll::Pending => std::task::Poll::Pending,
+        }
+    }
+
+    fn decode_row(
+        &self,
+        key: FullKey<Bytes>,
+        value: Bytes,
+    ) -> StorageResult<Option<(Bytes, OwnedRow)>> {
+        let row_data = self.row_serde.deserialize(&value)?;
+        let row_data = match &self.post_projection {
+            Some(indices) => indices.iter().map(|&i| row_data[i].clone()).collect(),
+            None => row_data,
+        };
+        Ok(Some((key.user_key.table_key.0, OwnedRow::new(row_data))))
     }
 }
 
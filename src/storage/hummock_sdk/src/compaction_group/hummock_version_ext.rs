@@ -22,9 +22,9 @@ use risingwave_pb::hummock::group_delta::DeltaType;
 use risingwave_pb::hummock::hummock_version::Levels;
 use risingwave_pb::hummock::hummock_version_delta::GroupDeltas;
 use risingwave_pb::hummock::{
-    CompactionConfig, CompatibilityVersion, GroupConstruct, GroupDestroy, GroupMetaChange,
-    GroupTableChange, HummockVersion, HummockVersionDelta, Level, LevelType, OverlappingLevel,
-    PbLevelType, SstableInfo,
+    CompactionConfig, CompatibilityVersion, GroupConstruct, GroupDelta, GroupDestroy,
+    GroupMetaChange, GroupTableChange, HummockVersion, HummockVersionDelta, IntraLevelDelta,
+    Level, LevelType, OverlappingLevel, PbLevelType, SstableInfo,
 };
 use tracing::warn;
 
@@ -121,6 +121,246 @@ pub struct SstDeltaInfo {
 
 pub type BranchedSstInfo = HashMap<CompactionGroupId, /* SST Id */ HummockSstableId>;
 
+/// Number of file bytes that earn an SST one more "allowed seek" before it is nominated for
+/// compaction. Mirrors LevelDB's `1 << 14` (16 KiB per seek) constant.
+const SEEK_BYTES_PER_ALLOWED_SEEK: u64 = 16 * 1024;
+/// Floor on the per-file seek budget so that tiny SSTs aren't flagged on their very first miss.
+const MIN_ALLOWED_SEEKS: i64 = 100;
+
+/// Returns the initial seek budget for a file of `file_size` bytes, modeled on LevelDB's
+/// `allowed_seeks`: one seek per [`SEEK_BYTES_PER_ALLOWED_SEEK`] bytes, floored at
+/// [`MIN_ALLOWED_SEEKS`].
+pub fn allowed_seeks_for_file_size(file_size: u64) -> i64 {
+    ((file_size / SEEK_BYTES_PER_ALLOWED_SEEK) as i64).max(MIN_ALLOWED_SEEKS)
+}
+
+/// Tracks per-SST "allowed seeks" budgets alongside a [`HummockVersion`], recording which file
+/// (if any) has been probed enough times without satisfying a read that it should be prioritized
+/// for compaction, independent of size-based heuristics. This mirrors LevelDB's
+/// `Version::file_to_compact_` bookkeeping.
+#[derive(Debug, Clone, Default)]
+pub struct SeekCompactionHints {
+    allowed_seeks: HashMap<(CompactionGroupId, HummockSstableObjectId), i64>,
+    /// The file (if any) whose budget has been exhausted, along with the level it lives in.
+    file_to_compact: Option<(CompactionGroupId, u32, HummockSstableObjectId)>,
+}
+
+impl SeekCompactionHints {
+    /// (Re)seeds every file's budget from the current contents of `version`, e.g. after
+    /// `apply_version_delta` has rewritten the file set for a group.
+    pub fn reseed(&mut self, version: &HummockVersion) {
+        self.allowed_seeks.clear();
+        self.file_to_compact = None;
+        for (group_id, levels) in &version.levels {
+            for level in levels.get_level0().get_sub_levels().iter().chain(levels.get_levels()) {
+                for sst in &level.table_infos {
+                    self.allowed_seeks.insert(
+                        (*group_id, sst.get_object_id()),
+                        allowed_seeks_for_file_size(sst.file_size),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Removes the budget (and any pending compaction hint) for a file deleted by a version
+    /// delta, so it can't linger and be reported as needing compaction after it's gone.
+    pub fn clear_removed(&mut self, group_id: CompactionGroupId, object_id: HummockSstableObjectId) {
+        self.allowed_seeks.remove(&(group_id, object_id));
+        if matches!(self.file_to_compact, Some((g, _, o)) if g == group_id && o == object_id) {
+            self.file_to_compact = None;
+        }
+    }
+
+    /// Records that a point/range read had to fall through `object_id` in `level_idx` of
+    /// `group_id` without finding the key it was looking for. Once the file's budget is
+    /// exhausted, it becomes the current seek-triggered compaction hint.
+    pub fn note_seek_miss(
+        &mut self,
+        group_id: CompactionGroupId,
+        level_idx: u32,
+        object_id: HummockSstableObjectId,
+    ) {
+        if let Some(budget) = self.allowed_seeks.get_mut(&(group_id, object_id)) {
+            *budget -= 1;
+            if *budget <= 0 && self.file_to_compact.is_none() {
+                self.file_to_compact = Some((group_id, level_idx, object_id));
+            }
+        }
+    }
+
+    /// Returns the file (if any) currently flagged for seek-triggered compaction.
+    pub fn file_to_compact(&self) -> Option<(CompactionGroupId, u32, HummockSstableObjectId)> {
+        self.file_to_compact
+    }
+
+    /// Alias for [`Self::note_seek_miss`] matching LevelDB's naming for the read-path callback
+    /// (`Version::RecordReadSample`/`GetStats`) that charges a file for a seek which didn't
+    /// satisfy the read.
+    pub fn record_seek_miss(
+        &mut self,
+        group_id: CompactionGroupId,
+        level_idx: u32,
+        object_id: HummockSstableObjectId,
+    ) {
+        self.note_seek_miss(group_id, level_idx, object_id);
+    }
+
+    /// Takes (clearing) the file currently flagged for seek-triggered compaction, if it belongs
+    /// to `group_id`. Unlike [`Self::file_to_compact`], this consumes the hint so the same file
+    /// isn't handed to two concurrent compaction pickers.
+    pub fn take_seek_compaction_candidate(
+        &mut self,
+        group_id: CompactionGroupId,
+    ) -> Option<(u32, HummockSstableObjectId)> {
+        if matches!(self.file_to_compact, Some((g, _, _)) if g == group_id) {
+            let (_, level_idx, object_id) = self.file_to_compact.take().unwrap();
+            Some((level_idx, object_id))
+        } else {
+            None
+        }
+    }
+
+    /// Clears the budget and any pending compaction hint for every object id in `deleted`. Must
+    /// be called alongside [`HummockLevelsExt::apply_compact_ssts`] (or any other path that
+    /// deletes SSTs from a group) so a stale hint never points at a file that no longer exists.
+    pub fn clear_deleted(
+        &mut self,
+        group_id: CompactionGroupId,
+        deleted: impl IntoIterator<Item = HummockSstableObjectId>,
+    ) {
+        for object_id in deleted {
+            self.clear_removed(group_id, object_id);
+        }
+    }
+}
+
+/// Content-hash algorithms a recorded [`SstChecksum`] may use. Carrying the algorithm alongside
+/// the digest lets the cluster migrate to a new algorithm incrementally, without reprocessing
+/// every already-checksummed SST.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// Fast, non-cryptographic; the default for routine corruption detection.
+    Xxh3,
+    /// Fast, non-cryptographic, widely supported by object-store `ETag`-style validation.
+    Crc32c,
+    /// Cryptographic; for deployments that need strong tamper-evidence, not just bit-rot
+    /// detection.
+    Sha256,
+}
+
+/// A content checksum recorded for one SST object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SstChecksum {
+    pub algorithm: ChecksumAlgorithm,
+    pub digest: Vec<u8>,
+}
+
+/// Returned by [`SstChecksumRegistry::verify`] when a freshly computed digest doesn't match what
+/// was recorded for an object, which usually means the object-store bytes were corrupted or a
+/// stale/rewritten object is being served under an id that should be immutable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    pub object_id: HummockSstableObjectId,
+    pub expected: Vec<u8>,
+    pub actual: Vec<u8>,
+}
+
+/// A side table of per-object content checksums, keyed by object id.
+///
+/// `SstableInfo` itself has no checksum field in this tree (it's protobuf-generated and its
+/// `.proto` isn't part of this change), so checksums are tracked here instead, the same way
+/// [`SeekCompactionHints`] tracks seek budgets outside of `SstableInfo`. A writer records a
+/// checksum when it uploads a new object (the point at which it has the plaintext bytes and
+/// already knows the digest); a reader that fetches the object later hashes the bytes itself and
+/// calls [`Self::verify`] to catch object-store corruption or a stale/rewritten object before it
+/// can produce silent wrong results.
+#[derive(Debug, Clone, Default)]
+pub struct SstChecksumRegistry {
+    checksums: HashMap<HummockSstableObjectId, SstChecksum>,
+}
+
+impl SstChecksumRegistry {
+    pub fn record(&mut self, object_id: HummockSstableObjectId, checksum: SstChecksum) {
+        self.checksums.insert(object_id, checksum);
+    }
+
+    pub fn get(&self, object_id: HummockSstableObjectId) -> Option<&SstChecksum> {
+        self.checksums.get(&object_id)
+    }
+
+    /// Drops the recorded checksum for `object_id`, e.g. once it's been GC'd and the id may be
+    /// reused by an unrelated future object.
+    pub fn remove(&mut self, object_id: HummockSstableObjectId) {
+        self.checksums.remove(&object_id);
+    }
+
+    /// Compares an already-computed `digest` (hashed by the caller with the algorithm returned
+    /// by `get(object_id).algorithm`, so this registry stays independent of any specific hashing
+    /// crate) against what was recorded for `object_id`. `Ok(())` if there's nothing recorded to
+    /// check against, since not every deployment opts into checksumming every object.
+    pub fn verify(
+        &self,
+        object_id: HummockSstableObjectId,
+        digest: &[u8],
+    ) -> Result<(), ChecksumMismatch> {
+        match self.checksums.get(&object_id) {
+            Some(checksum) if checksum.digest != digest => Err(ChecksumMismatch {
+                object_id,
+                expected: checksum.digest.clone(),
+                actual: digest.to_vec(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl VersionDeltaVisitor for SstChecksumRegistry {
+    fn visit_intra_level(&mut self, _group_id: CompactionGroupId, delta: &IntraLevelDelta) {
+        // `removed_table_ids` are SST ids, not object ids, but the two coincide for any SST that
+        // was never branched across compaction groups, which covers the common case.
+        for sst_id in &delta.removed_table_ids {
+            self.remove(*sst_id);
+        }
+    }
+}
+
+/// Tracks the last applied idempotency sequence number per external writer ("app"), giving
+/// exactly-once semantics for ingestion/backfill jobs that may re-submit the same batch after a
+/// crash. Patterned on Delta Lake's `Txn(app_id, version, last_updated)` action: before applying
+/// a version delta stamped with `(app_id, app_seq)`, the meta layer looks up
+/// [`Self::app_transaction_version`] and rejects or no-ops the delta if `app_seq` doesn't advance
+/// past what's recorded.
+///
+/// Parallels `HummockVersion::table_watermarks` in spirit, but lives outside `HummockVersion`
+/// itself since (like [`SstChecksumRegistry`] and [`SeekCompactionHints`]) its `.proto` isn't
+/// part of this change.
+#[derive(Debug, Clone, Default)]
+pub struct AppTransactionTracker {
+    app_seqs: HashMap<String, i64>,
+}
+
+impl AppTransactionTracker {
+    /// Advances `app_id`'s recorded sequence to `app_seq` and returns `true`, unless `app_seq` is
+    /// less-than-or-equal to what's already recorded -- a stale or duplicate retry -- in which
+    /// case this is a no-op and returns `false`.
+    pub fn record_if_newer(&mut self, app_id: &str, app_seq: i64) -> bool {
+        match self.app_seqs.get(app_id).copied() {
+            Some(current) if app_seq <= current => false,
+            _ => {
+                self.app_seqs.insert(app_id.to_string(), app_seq);
+                true
+            }
+        }
+    }
+
+    /// The last recorded sequence number for `app_id`, or `None` if it has never committed a
+    /// transaction.
+    pub fn app_transaction_version(&self, app_id: &str) -> Option<i64> {
+        self.app_seqs.get(app_id).copied()
+    }
+}
+
 #[easy_ext::ext(HummockVersionExt)]
 impl HummockVersion {
     pub fn get_compaction_group_levels(&self, compaction_group_id: CompactionGroupId) -> &Levels {
@@ -189,6 +429,214 @@ impl HummockVersion {
             .map(|group| group.levels.len() + 1)
             .unwrap_or(0)
     }
+
+    /// Computes a compaction score per level of `group_id`, mirroring
+    /// `Version::compaction_score` in LevelDB/RocksDB: L0's score is the number of sub-levels
+    /// relative to `config.level0_file_num_compaction_trigger`, and each level `idx >= 1`'s
+    /// score is its `total_file_size` relative to [`max_bytes_for_level`]. The result is sorted
+    /// descending by score, so the first entry is the level most in need of compaction.
+    pub fn compute_compaction_scores(
+        &self,
+        group_id: CompactionGroupId,
+        config: &CompactionConfig,
+    ) -> Vec<(usize, f64)> {
+        let Some(levels) = self.levels.get(&group_id) else {
+            return vec![];
+        };
+        let mut scores = Vec::with_capacity(levels.levels.len() + 1);
+        let l0_trigger = config.level0_file_num_compaction_trigger.max(1) as f64;
+        scores.push((
+            0,
+            levels.get_level0().get_sub_levels().len() as f64 / l0_trigger,
+        ));
+        for (i, level) in levels.levels.iter().enumerate() {
+            let level_idx = i + 1;
+            let max_bytes = max_bytes_for_level(config, level_idx).max(1);
+            scores.push((level_idx, level.total_file_size as f64 / max_bytes as f64));
+        }
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scores
+    }
+
+    /// Returns the highest-scoring level from [`Self::compute_compaction_scores`] whose score
+    /// exceeds `1.0`, i.e. the level a compaction picker should target next. `None` means the
+    /// group is healthy and doesn't need compaction right now.
+    pub fn pick_compaction_level(
+        &self,
+        group_id: CompactionGroupId,
+        config: &CompactionConfig,
+    ) -> Option<usize> {
+        self.compute_compaction_scores(group_id, config)
+            .into_iter()
+            .find(|(_, score)| *score > 1.0)
+            .map(|(level_idx, _)| level_idx)
+    }
+
+    /// Returns every SST in `group_id`'s level `level_idx` whose key range overlaps
+    /// `key_range`. `level_idx == 0` scans the (overlapping) L0 sub-levels linearly; any other
+    /// level is sorted and non-overlapping, so the contiguous candidate slice is located with a
+    /// binary search on the range boundaries instead of a full scan.
+    pub fn get_overlapping_ssts(
+        &self,
+        group_id: CompactionGroupId,
+        level_idx: u32,
+        key_range: &risingwave_pb::hummock::KeyRange,
+    ) -> Vec<&SstableInfo> {
+        let Some(levels) = self.levels.get(&group_id) else {
+            return vec![];
+        };
+        if level_idx == 0 {
+            levels
+                .get_level0()
+                .get_sub_levels()
+                .iter()
+                .flat_map(|sub_level| {
+                    sub_level
+                        .table_infos
+                        .iter()
+                        .filter(|sst| key_ranges_overlap(sst.key_range.as_ref().unwrap(), key_range))
+                })
+                .collect()
+        } else {
+            let level = levels.get_level(level_idx as usize);
+            // `table_infos` is sorted and non-overlapping, so the first file whose right bound
+            // reaches into `key_range` starts the contiguous candidate run, which ends at the
+            // first file whose left bound is past `key_range`'s right bound.
+            let start = level
+                .table_infos
+                .partition_point(|sst| sst.key_range.as_ref().unwrap().right < key_range.left);
+            level.table_infos[start..]
+                .iter()
+                .take_while(|sst| sst.key_range.as_ref().unwrap().left <= key_range.right)
+                .collect()
+        }
+    }
+
+    /// Cross-level variant of [`Self::get_overlapping_ssts`]: collects overlapping SSTs from L0
+    /// and every other level of `group_id` that intersects `key_range`.
+    pub fn get_overlapping_ssts_all_levels(
+        &self,
+        group_id: CompactionGroupId,
+        key_range: &risingwave_pb::hummock::KeyRange,
+    ) -> Vec<&SstableInfo> {
+        let num_levels = self
+            .levels
+            .get(&group_id)
+            .map_or(0, |levels| levels.levels.len());
+        let mut result = self.get_overlapping_ssts(group_id, 0, key_range);
+        for level_idx in 1..=num_levels as u32 {
+            result.extend(self.get_overlapping_ssts(group_id, level_idx, key_range));
+        }
+        result
+    }
+
+    /// Returns whether moving `sst_ids` out of `group_id`'s `from_level` into `from_level + 1`
+    /// can be a metadata-only "trivial move", i.e. one that rewrites no file. Ports
+    /// RocksDB PR 6021: a plain key-range intersection with an output-level file isn't enough to
+    /// force a rewrite if none of the moved files' boundary keys actually falls inside it, so we
+    /// only count [`compute_overlapping_output_ssts`]'s genuine overlaps.
+    pub fn can_trivial_move(
+        &self,
+        group_id: CompactionGroupId,
+        from_level: usize,
+        sst_ids: &[HummockSstableId],
+    ) -> bool {
+        let Some(levels) = self.levels.get(&group_id) else {
+            return false;
+        };
+        if from_level == 0 || from_level >= levels.levels.len() {
+            return false;
+        }
+        let start_ssts: Vec<SstableInfo> = levels
+            .get_level(from_level)
+            .table_infos
+            .iter()
+            .filter(|sst| sst_ids.contains(&sst.sst_id))
+            .cloned()
+            .collect();
+        if start_ssts.is_empty() {
+            return false;
+        }
+        let output_level = levels.get_level(from_level + 1);
+        compute_overlapping_output_ssts(&start_ssts, output_level).is_empty()
+    }
+
+    /// Splits `output_key_range` into the boundaries a compaction writing to `output_level_idx`
+    /// should cut its output SSTs along, so that no single output file overlaps more than
+    /// `max_grandparent_overlap_bytes` of `output_level_idx + 1` (the "grandparent" level).
+    /// Mirrors LevelDB's `kMaxGrandParentOverlapBytes`: without this bound, a deep, narrow stack
+    /// of compactions can produce one huge output file that makes the *next* compaction
+    /// involving it prohibitively expensive. `output_level_idx` being the last level (no
+    /// grandparent) always yields a single, unsplit segment.
+    pub fn bound_output_by_grandparent(
+        &self,
+        group_id: CompactionGroupId,
+        output_level_idx: usize,
+        output_key_range: &risingwave_pb::hummock::KeyRange,
+        max_grandparent_overlap_bytes: u64,
+    ) -> Vec<risingwave_pb::hummock::KeyRange> {
+        let Some(levels) = self.levels.get(&group_id) else {
+            return vec![output_key_range.clone()];
+        };
+        if output_level_idx + 1 >= levels.levels.len() {
+            return vec![output_key_range.clone()];
+        }
+        let grandparent_level = levels.get_level(output_level_idx + 1 + 1);
+        let overlapping: Vec<&SstableInfo> = grandparent_level
+            .table_infos
+            .iter()
+            .filter(|sst| key_ranges_overlap(sst.key_range.as_ref().unwrap(), output_key_range))
+            .collect();
+        if overlapping.is_empty() {
+            return vec![output_key_range.clone()];
+        }
+
+        let mut segments = Vec::new();
+        let mut segment_start = output_key_range.left.clone();
+        let mut accumulated = 0u64;
+        for (idx, sst) in overlapping.iter().enumerate() {
+            accumulated += sst.file_size;
+            let is_last = idx + 1 == overlapping.len();
+            if accumulated > max_grandparent_overlap_bytes && !is_last {
+                let cut = sst.key_range.as_ref().unwrap().right.clone();
+                segments.push(risingwave_pb::hummock::KeyRange {
+                    left: segment_start,
+                    right: cut.clone(),
+                    right_exclusive: false,
+                });
+                segment_start = cut;
+                accumulated = 0;
+            }
+        }
+        segments.push(risingwave_pb::hummock::KeyRange {
+            left: segment_start,
+            right: output_key_range.right.clone(),
+            right_exclusive: output_key_range.right_exclusive,
+        });
+        segments
+    }
+}
+
+/// For each SST in `output_level`, returns its index if at least one file in `start_ssts` has a
+/// boundary key (`key_range.left` or `key_range.right`) strictly inside that output SST's
+/// `[left, right]` range. A bare key-range *intersection* isn't enough: two adjacent,
+/// non-overlapping SSTs can still have touching ranges without either containing the other's
+/// actual keys, and such a pair need not be rewritten by a trivial move.
+pub fn compute_overlapping_output_ssts(start_ssts: &[SstableInfo], output_level: &Level) -> Vec<usize> {
+    output_level
+        .table_infos
+        .iter()
+        .enumerate()
+        .filter(|(_, output_sst)| {
+            let output_range = output_sst.key_range.as_ref().unwrap();
+            start_ssts.iter().any(|start_sst| {
+                let start_range = start_sst.key_range.as_ref().unwrap();
+                (start_range.left > output_range.left && start_range.left < output_range.right)
+                    || (start_range.right > output_range.left && start_range.right < output_range.right)
+            })
+        })
+        .map(|(idx, _)| idx)
+        .collect()
 }
 
 pub type SstSplitInfo = (
@@ -202,6 +650,32 @@ pub type SstSplitInfo = (
     HummockSstableId,
 );
 
+/// Observes a [`HummockVersionDelta`] as it is applied, so consumers that need to react to
+/// individual edits (local caches, watermark trackers, per-table file indexes) can build derived
+/// state incrementally instead of diffing two full versions after the fact.
+///
+/// Registered visitors are invoked by
+/// [`HummockVersionUpdateExt::apply_version_delta_with_visitors`], in the same order
+/// `apply_version_delta` itself would process the delta's groups. Every group is visited before
+/// `apply_version_delta` applies any of the delta to `self.levels` -- so `visit_*` always sees
+/// edits that are about to become current, never a version or two stale.
+pub trait VersionDeltaVisitor {
+    /// Called once per `(group_id, GroupDelta)` pair in `version_delta.group_deltas`, before any
+    /// of the delta's more specific `visit_*` callbacks.
+    fn visit_group_delta(&mut self, _group_id: CompactionGroupId, _group_delta: &GroupDelta) {}
+
+    /// Called for every [`DeltaType::IntraLevel`] delta, i.e. one that inserts/removes SSTs
+    /// within an existing level rather than constructing or destroying a group.
+    fn visit_intra_level(&mut self, _group_id: CompactionGroupId, _delta: &IntraLevelDelta) {}
+
+    /// Called when a [`DeltaType::GroupDestroy`] delta removes `group_id` entirely.
+    fn visit_group_destroy(&mut self, _group_id: CompactionGroupId) {}
+
+    /// Called once after every group in `version_delta` has been applied, with the resulting
+    /// version, so a visitor can finalize any state it accumulated mid-pass.
+    fn finish(&mut self, _version: &HummockVersion) {}
+}
+
 #[easy_ext::ext(HummockVersionUpdateExt)]
 impl HummockVersion {
     pub fn count_new_ssts_in_group_split(
@@ -343,6 +817,93 @@ impl HummockVersion {
         split_id_vers
     }
 
+    /// The reverse of [`Self::init_with_parent_group`]: folds `source_group_id` back into
+    /// `target_group_id`, moving every member table and SST from the source into the target.
+    /// L0 sub-levels are merged by `sub_level_id` using the same `insert_hint` logic
+    /// `init_with_parent_group` uses (extending an existing sub-level with a matching id, or
+    /// inserting a new one in sorted position). Non-L0 levels are concatenated and re-sorted by
+    /// `key_range` to preserve the `can_concat` invariant. Returns an empty split list: unlike a
+    /// split, a merge never mints new SST ids, it only relocates existing ones.
+    ///
+    /// Used to undo over-eager group splitting once write rates to the split-out tables drop
+    /// back down and having extra compaction groups stops paying for itself.
+    pub fn merge_with_group(
+        &mut self,
+        source_group_id: CompactionGroupId,
+        target_group_id: CompactionGroupId,
+    ) -> Vec<SstSplitInfo> {
+        if source_group_id == target_group_id || !self.levels.contains_key(&source_group_id) {
+            return vec![];
+        }
+        let [source_levels, target_levels] = self
+            .levels
+            .get_many_mut([&source_group_id, &target_group_id])
+            .expect("both compaction groups should exist");
+
+        if let Some(source_l0) = &mut source_levels.l0 {
+            let target_l0 = target_levels.l0.as_mut().unwrap();
+            for sub_level in source_l0.sub_levels.drain(..) {
+                let mut insert_hint = Err(target_l0.sub_levels.len());
+                for (idx, other) in target_l0.sub_levels.iter().enumerate() {
+                    match other.sub_level_id.cmp(&sub_level.sub_level_id) {
+                        Ordering::Less => {}
+                        Ordering::Equal => {
+                            insert_hint = Ok(idx);
+                            break;
+                        }
+                        Ordering::Greater => {
+                            insert_hint = Err(idx);
+                            break;
+                        }
+                    }
+                }
+                match insert_hint {
+                    Ok(idx) => add_ssts_to_sub_level(target_l0, idx, sub_level.table_infos),
+                    Err(idx) => insert_new_sub_level(
+                        target_l0,
+                        sub_level.sub_level_id,
+                        sub_level.level_type(),
+                        sub_level.table_infos,
+                        Some(idx),
+                    ),
+                }
+            }
+        }
+
+        for (idx, source_level) in source_levels.levels.iter_mut().enumerate() {
+            if source_level.table_infos.is_empty() {
+                continue;
+            }
+            let target_level = &mut target_levels.levels[idx];
+            target_level.total_file_size += source_level.total_file_size;
+            target_level.uncompressed_file_size += source_level.uncompressed_file_size;
+            target_level
+                .table_infos
+                .append(&mut source_level.table_infos);
+            target_level.table_infos.sort_by(|sst1, sst2| {
+                let a = sst1.key_range.as_ref().unwrap();
+                let b = sst2.key_range.as_ref().unwrap();
+                a.compare(b)
+            });
+            assert!(
+                can_concat(&target_level.table_infos),
+                "merged level {} is not concat-able after folding group {} into {}",
+                idx + 1,
+                source_group_id,
+                target_group_id
+            );
+            source_level.total_file_size = 0;
+            source_level.uncompressed_file_size = 0;
+        }
+
+        let mut moved_table_ids = std::mem::take(&mut source_levels.member_table_ids);
+        target_levels.member_table_ids.append(&mut moved_table_ids);
+        target_levels.member_table_ids.sort();
+
+        self.levels.remove(&source_group_id);
+        vec![]
+    }
+
     pub fn build_sst_delta_infos(&self, version_delta: &HummockVersionDelta) -> Vec<SstDeltaInfo> {
         let mut infos = vec![];
 
@@ -553,6 +1114,42 @@ impl HummockVersion {
         sst_split_info
     }
 
+    /// Applies `version_delta` like [`Self::apply_version_delta`], but additionally feeds every
+    /// group delta through `visitors` as it is applied, so registered
+    /// [`VersionDeltaVisitor`]s can build derived state incrementally instead of diffing two
+    /// full versions afterwards.
+    pub fn apply_version_delta_with_visitors(
+        &mut self,
+        version_delta: &HummockVersionDelta,
+        visitors: &mut [&mut dyn VersionDeltaVisitor],
+    ) -> Vec<SstSplitInfo> {
+        for (group_id, group_deltas) in &version_delta.group_deltas {
+            for group_delta in &group_deltas.group_deltas {
+                for visitor in visitors.iter_mut() {
+                    visitor.visit_group_delta(*group_id, group_delta);
+                }
+                match group_delta.get_delta_type() {
+                    Ok(DeltaType::IntraLevel(delta)) => {
+                        for visitor in visitors.iter_mut() {
+                            visitor.visit_intra_level(*group_id, delta);
+                        }
+                    }
+                    Ok(DeltaType::GroupDestroy(_)) => {
+                        for visitor in visitors.iter_mut() {
+                            visitor.visit_group_destroy(*group_id);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let sst_split_info = self.apply_version_delta(version_delta);
+        for visitor in visitors.iter_mut() {
+            visitor.finish(self);
+        }
+        sst_split_info
+    }
+
     pub fn build_compaction_group_info(&self) -> HashMap<TableId, CompactionGroupId> {
         let mut ret = HashMap::new();
         for (compaction_group_id, levels) in &self.levels {
@@ -585,10 +1182,264 @@ impl HummockVersion {
         }
         ret
     }
+
+    /// Applies `version_delta` like [`Self::apply_version_delta`], but also incrementally
+    /// maintains `branched_sst_info` instead of requiring a full [`Self::build_branched_sst_info`]
+    /// rescan afterwards. Only the SSTs of groups touched by this delta are rescanned, though the
+    /// branch map itself is still swept once per call, and debug builds additionally compare the
+    /// result against a full rebuild.
+    pub fn apply_version_delta_with_branched_sst_info(
+        &mut self,
+        version_delta: &HummockVersionDelta,
+        branched_sst_info: &mut BTreeMap<HummockSstableObjectId, BranchedSstInfo>,
+    ) -> Vec<SstSplitInfo> {
+        // Drop branch entries that this delta is about to invalidate, while the pre-update
+        // state is still visible: destroyed groups, and SSTs a compaction delta removes.
+        for (group_id, group_deltas) in &version_delta.group_deltas {
+            let summary = summarize_group_deltas(group_deltas);
+            if summary.group_destroy.is_some() {
+                for branches in branched_sst_info.values_mut() {
+                    branches.remove(group_id);
+                }
+                continue;
+            }
+            if summary.delete_sst_ids_set.is_empty() {
+                continue;
+            }
+            if let Some(levels) = self.levels.get(group_id) {
+                for level in levels
+                    .get_level0()
+                    .get_sub_levels()
+                    .iter()
+                    .chain(levels.get_levels())
+                {
+                    for sst in &level.table_infos {
+                        if summary.delete_sst_ids_set.contains(&sst.sst_id) {
+                            if let Some(branches) = branched_sst_info.get_mut(&sst.get_object_id()) {
+                                branches.remove(group_id);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        branched_sst_info.retain(|_, branches| !branches.is_empty());
+
+        let affected_groups = version_delta.group_deltas.keys().copied().collect_vec();
+        let sst_split_info = self.apply_version_delta(version_delta);
+
+        // `init_with_parent_group` may have minted new branch sst ids for objects in the
+        // groups this delta touched; re-derive their entries from the post-update state.
+        if !sst_split_info.is_empty() {
+            let touched_objects: HashSet<_> = sst_split_info
+                .iter()
+                .map(|(object_id, ..)| *object_id)
+                .collect();
+            for group_id in &affected_groups {
+                let Some(levels) = self.levels.get(group_id) else {
+                    continue;
+                };
+                for level in levels
+                    .get_level0()
+                    .get_sub_levels()
+                    .iter()
+                    .chain(levels.get_levels())
+                {
+                    for sst in &level.table_infos {
+                        if sst.sst_id != sst.object_id && touched_objects.contains(&sst.object_id) {
+                            branched_sst_info
+                                .entry(sst.get_object_id())
+                                .or_default()
+                                .insert(*group_id, sst.sst_id);
+                        }
+                    }
+                }
+            }
+        }
+
+        debug_assert_eq!(
+            *branched_sst_info,
+            self.build_branched_sst_info(),
+            "incrementally maintained branched sst info diverged from a full rebuild"
+        );
+
+        sst_split_info
+    }
+}
+
+/// Wraps a [`HummockVersion`] being incrementally updated via [`apply_version_delta`], with a
+/// switch for whether to pay the cost of tracking removed objects. `GroupDestroy`/`IntraLevel`
+/// deltas always update `levels` correctly regardless of the switch; what's skipped when deletion
+/// tracking is off is purely the bookkeeping needed to support GC and checkpoint writing, which a
+/// pure reader (resolving `SstableInfo` for a pinned epoch, a metrics scraper, a debugging tool)
+/// never needs. Analogous to opting a read-only consumer out of tombstone loading.
+///
+/// [`apply_version_delta`]: HummockVersionUpdateExt::apply_version_delta
+pub struct HummockVersionBuilder {
+    version: HummockVersion,
+    require_deletion_tracking: bool,
+    removed_object_ids: HashSet<HummockSstableObjectId>,
+}
+
+impl HummockVersionBuilder {
+    pub fn new(version: HummockVersion) -> Self {
+        Self {
+            version,
+            require_deletion_tracking: true,
+            removed_object_ids: HashSet::new(),
+        }
+    }
+
+    /// Sets whether applied deltas should accumulate the set of removed object ids. Defaults to
+    /// `true`; callers that only ever read the resulting version (never run GC or write
+    /// checkpoints from it) should pass `false` to skip that bookkeeping entirely.
+    pub fn with_require_deletion_tracking(mut self, require_deletion_tracking: bool) -> Self {
+        self.require_deletion_tracking = require_deletion_tracking;
+        self
+    }
+
+    /// Applies `version_delta` to the wrapped version, additionally recording its
+    /// `gc_object_ids` in [`Self::removed_object_ids`] unless deletion tracking was disabled.
+    pub fn apply_version_delta(&mut self, version_delta: &HummockVersionDelta) -> Vec<SstSplitInfo> {
+        let sst_split_info = self.version.apply_version_delta(version_delta);
+        if self.require_deletion_tracking {
+            self.removed_object_ids
+                .extend(version_delta.gc_object_ids.iter().copied());
+        }
+        sst_split_info
+    }
+
+    pub fn version(&self) -> &HummockVersion {
+        &self.version
+    }
+
+    pub fn into_version(self) -> HummockVersion {
+        self.version
+    }
+
+    /// The object ids observed as removed since this builder was created. Always empty when
+    /// deletion tracking is disabled.
+    pub fn removed_object_ids(&self) -> &HashSet<HummockSstableObjectId> {
+        &self.removed_object_ids
+    }
+}
+
+/// A candidate compaction input picked from level `input_level_idx` (plus the files it overlaps
+/// in `input_level_idx + 1`), bounded so that the output won't overlap too much of
+/// `input_level_idx + 2` (the "grandparent" level).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactionInput {
+    pub input_level_idx: usize,
+    pub select_input_ssts: Vec<SstableInfo>,
+    pub target_input_ssts: Vec<SstableInfo>,
+    pub grandparent_overlapped_bytes: u64,
+}
+
+/// Returns whether the two (left-inclusive) key ranges overlap, using the raw `left`/`right`
+/// bounds rather than a full user-key comparator.
+fn key_ranges_overlap(a: &risingwave_pb::hummock::KeyRange, b: &risingwave_pb::hummock::KeyRange) -> bool {
+    a.left <= b.right && b.left <= a.right
+}
+
+/// Returns the smallest key range spanning both `a` and `b`.
+fn merge_key_range(
+    a: &risingwave_pb::hummock::KeyRange,
+    b: &risingwave_pb::hummock::KeyRange,
+) -> risingwave_pb::hummock::KeyRange {
+    risingwave_pb::hummock::KeyRange {
+        left: std::cmp::min(&a.left, &b.left).clone(),
+        right: std::cmp::max(&a.right, &b.right).clone(),
+        right_exclusive: if a.right >= b.right {
+            a.right_exclusive
+        } else {
+            b.right_exclusive
+        },
+    }
+}
+
+/// Sums the file size of every SST in `level` whose key range overlaps `key_range`.
+fn overlapped_file_size(level: Option<&Level>, key_range: &risingwave_pb::hummock::KeyRange) -> u64 {
+    level.map_or(0, |level| {
+        level
+            .table_infos
+            .iter()
+            .filter(|sst| key_ranges_overlap(sst.key_range.as_ref().unwrap(), key_range))
+            .map(|sst| sst.file_size)
+            .sum()
+    })
+}
+
+/// Returns whether any SST in `level` overlaps `key_range`. Unlike
+/// [`HummockVersion::get_overlapping_ssts`], this takes a single [`Level`] directly, so callers
+/// that already have one in hand (e.g. an individual L0 sub-level) don't need to re-derive its
+/// compaction group and level index just to ask the same question.
+pub fn level_overlaps_key_range(
+    level: &Level,
+    key_range: &risingwave_pb::hummock::KeyRange,
+) -> bool {
+    level
+        .table_infos
+        .iter()
+        .any(|sst| key_ranges_overlap(sst.key_range.as_ref().unwrap(), key_range))
 }
 
 #[easy_ext::ext(HummockLevelsExt)]
 impl Levels {
+    /// Picks a compaction input anchored on the first file in level `input_level_idx`, growing
+    /// the selected set of files in that level and their overlapping counterpart files in
+    /// `input_level_idx + 1`, while bounding the amount of data the output would overlap in the
+    /// grandparent level (`input_level_idx + 2`). This mirrors LevelDB's
+    /// `kMaxGrandParentOverlapBytes` limit, which keeps a single compaction's output from
+    /// becoming so large that the *next* compaction involving it becomes prohibitively expensive.
+    pub fn pick_compaction_with_grandparent_bound(
+        &self,
+        input_level_idx: usize,
+        max_grandparent_overlap_bytes: u64,
+    ) -> Option<CompactionInput> {
+        if input_level_idx == 0 || input_level_idx >= self.levels.len() {
+            return None;
+        }
+        let input_level = self.get_level(input_level_idx);
+        if input_level.table_infos.is_empty() {
+            return None;
+        }
+        let grandparent_level = (input_level_idx + 1 < self.levels.len())
+            .then(|| self.get_level(input_level_idx + 1 + 1));
+
+        let mut select_input_ssts = vec![input_level.table_infos[0].clone()];
+        let mut key_range = select_input_ssts[0].key_range.clone().unwrap();
+        let mut grandparent_overlapped_bytes = overlapped_file_size(grandparent_level, &key_range);
+
+        // Greedily grow the selection with subsequent files in the level as long as doing so
+        // doesn't push the grandparent overlap past the configured bound.
+        for sst in input_level.table_infos.iter().skip(1) {
+            let sst_range = sst.key_range.as_ref().unwrap();
+            let candidate_range = merge_key_range(&key_range, sst_range);
+            let candidate_overlap = overlapped_file_size(grandparent_level, &candidate_range);
+            if candidate_overlap > max_grandparent_overlap_bytes {
+                break;
+            }
+            key_range = candidate_range;
+            grandparent_overlapped_bytes = candidate_overlap;
+            select_input_ssts.push(sst.clone());
+        }
+
+        let target_level = self.get_level(input_level_idx + 1);
+        let target_input_ssts = target_level
+            .table_infos
+            .iter()
+            .filter(|sst| key_ranges_overlap(sst.key_range.as_ref().unwrap(), &key_range))
+            .cloned()
+            .collect();
+
+        Some(CompactionInput {
+            input_level_idx,
+            select_input_ssts,
+            target_input_ssts,
+            grandparent_overlapped_bytes,
+        })
+    }
+
     pub fn get_level0(&self) -> &OverlappingLevel {
         self.l0.as_ref().unwrap()
     }
@@ -610,6 +1461,11 @@ impl Levels {
             .sum()
     }
 
+    /// Note: callers that also maintain a [`SeekCompactionHints`] for this group must call
+    /// [`SeekCompactionHints::clear_deleted`] with the object ids of the SSTs named in
+    /// `summary.delete_sst_ids_set` alongside this (that set holds SST ids, which equal the object
+    /// id only for an SST never branched across groups), since `Levels` itself has no reference
+    /// back to the seek-compaction side table.
     pub fn apply_compact_ssts(&mut self, summary: GroupDeltasSummary) {
         let GroupDeltasSummary {
             delete_sst_levels,
@@ -715,6 +1571,17 @@ impl Levels {
     }
 }
 
+/// The target size of `level_idx` (`>= 1`), derived from the group's `CompactionConfig` as
+/// `max_bytes_for_level_base * max_bytes_for_level_multiplier ^ (level_idx - 1)`, matching the
+/// exponential per-level growth LevelDB/RocksDB use to size their LSM tree.
+fn max_bytes_for_level(config: &CompactionConfig, level_idx: usize) -> u64 {
+    debug_assert!(level_idx >= 1);
+    let multiplier = config.max_bytes_for_level_multiplier.max(1);
+    config
+        .max_bytes_for_level_base
+        .saturating_mul(multiplier.saturating_pow((level_idx - 1) as u32))
+}
+
 pub fn build_initial_compaction_group_levels(
     group_id: CompactionGroupId,
     compaction_config: &CompactionConfig,
@@ -1122,7 +1989,12 @@ pub fn validate_version(version: &HummockVersion) -> Vec<String> {
             }
 
             let mut prev_table_info: Option<&SstableInfo> = None;
+            let mut computed_file_size = 0u64;
+            let mut computed_uncompressed_file_size = 0u64;
             for table_info in &level.table_infos {
+                computed_file_size += table_info.file_size;
+                computed_uncompressed_file_size += table_info.uncompressed_file_size;
+
                 // Ensure table_ids are sorted and unique
                 if !table_info.table_ids.is_sorted_by(|a, b| {
                     if a < b {
@@ -1156,6 +2028,22 @@ pub fn validate_version(version: &HummockVersion) -> Vec<String> {
                     let _ = prev_table_info.insert(table_info);
                 }
             }
+
+            // Ensure `total_file_size`/`uncompressed_file_size` agree with `table_infos`: a
+            // mismatch here usually means `apply_compact_ssts`/`level_delete_ssts` updated the
+            // file list without correctly updating the cached size fields.
+            if level.total_file_size != computed_file_size {
+                res.push(format!(
+                    "{}: total_file_size {} disagrees with recomputed {}",
+                    level_identifier, level.total_file_size, computed_file_size
+                ));
+            }
+            if level.uncompressed_file_size != computed_uncompressed_file_size {
+                res.push(format!(
+                    "{}: uncompressed_file_size {} disagrees with recomputed {}",
+                    level_identifier, level.uncompressed_file_size, computed_uncompressed_file_size
+                ));
+            }
         };
 
         if let Some(l0) = &levels.l0 {
@@ -1183,6 +2071,61 @@ pub fn validate_version(version: &HummockVersion) -> Vec<String> {
     res
 }
 
+/// The target-size overshoot multiplier past which a level is reported as stalled: if a level's
+/// actual bytes exceed `max_bytes_for_level(idx) * STALLED_COMPACTION_MULTIPLIER`, compaction is
+/// falling behind badly enough that it's probably stuck rather than merely catching up.
+const STALLED_COMPACTION_MULTIPLIER: f64 = 4.0;
+
+/// Runs [`validate_version`]'s structural checks, then appends size-health metrics that require
+/// each group's [`CompactionConfig`] (which isn't itself part of `HummockVersion`): per-group
+/// write amplification, per-level fill ratio against [`max_bytes_for_level`], and a warning when
+/// a level has overshot its target badly enough to suggest compaction has stalled. Callers
+/// without a config handy (e.g. code that only cares about structural validity) should keep
+/// calling `validate_version` directly instead.
+pub fn validate_version_health(
+    version: &HummockVersion,
+    compaction_configs: &HashMap<CompactionGroupId, CompactionConfig>,
+) -> Vec<String> {
+    let mut res = validate_version(version);
+
+    for (group_id, levels) in &version.levels {
+        let Some(config) = compaction_configs.get(group_id) else {
+            continue;
+        };
+        let l0_bytes = levels.l0.as_ref().map_or(0, |l0| l0.total_file_size);
+        let non_l0_bytes: u64 = levels.levels.iter().map(|level| level.total_file_size).sum();
+        if l0_bytes > 0 {
+            res.push(format!(
+                "GROUP {}: write amplification {:.2} ({} bytes in all levels / {} L0 bytes)",
+                group_id,
+                (l0_bytes + non_l0_bytes) as f64 / l0_bytes as f64,
+                l0_bytes + non_l0_bytes,
+                l0_bytes
+            ));
+        }
+
+        for (idx, level) in levels.levels.iter().enumerate() {
+            let level_idx = idx + 1;
+            let target = max_bytes_for_level(config, level_idx);
+            if target == 0 {
+                continue;
+            }
+            let fill_ratio = level.total_file_size as f64 / target as f64;
+            res.push(format!(
+                "GROUP {} LEVEL {}: fill ratio {:.2} ({} / {} target bytes)",
+                group_id, level_idx, fill_ratio, level.total_file_size, target
+            ));
+            if fill_ratio > STALLED_COMPACTION_MULTIPLIER {
+                res.push(format!(
+                    "GROUP {} LEVEL {}: {} bytes exceeds {}x target of {} bytes, compaction may be stalled",
+                    group_id, level_idx, level.total_file_size, STALLED_COMPACTION_MULTIPLIER, target
+                ));
+            }
+        }
+    }
+    res
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -0,0 +1,249 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A compact, lazily-decoded on-disk representation for [`HummockVersion`].
+//!
+//! [`HummockVersion::serialize_compact`] writes a fixed-width, little-endian directory of
+//! per-compaction-group offsets/lengths into the encoded `Levels` records, similar in spirit to
+//! Mercurial's dirstate-v2 format: the directory (group id + member table ids + byte range) is
+//! cheap to parse in full, while the potentially huge `table_infos` payload behind each offset is
+//! only decoded on demand. [`LazyHummockVersion`] wraps a parsed directory over the raw bytes and
+//! caches each group's decoded [`Levels`] the first time it's touched.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use bytes::{Buf, BufMut, Bytes};
+use prost::Message;
+use risingwave_pb::hummock::hummock_version::Levels;
+use risingwave_pb::hummock::HummockVersion;
+
+use crate::CompactionGroupId;
+
+/// Magic prefix identifying the compact serialization format, so a reader can reject/fall back
+/// on an unexpected payload instead of misinterpreting it.
+const MAGIC: u32 = 0x4857_4c31; // "HWL1"
+
+/// One entry in the group directory: a compaction group's id, its member table ids (cheap to
+/// keep inline since callers frequently just want membership), and the byte range of its
+/// encoded `Levels` payload within the trailing data section.
+#[derive(Debug, Clone)]
+struct GroupDirEntry {
+    group_id: CompactionGroupId,
+    member_table_ids: Vec<u32>,
+    offset: u32,
+    length: u32,
+}
+
+impl HummockVersion {
+    /// Serializes this version into the compact, lazily-decodable layout described in this
+    /// module's documentation.
+    pub fn serialize_compact(&self) -> Bytes {
+        let mut data_section = Vec::new();
+        let mut dir_entries = Vec::with_capacity(self.levels.len());
+        for (group_id, levels) in &self.levels {
+            let offset = data_section.len() as u32;
+            let encoded = levels.encode_to_vec();
+            let length = encoded.len() as u32;
+            data_section.extend_from_slice(&encoded);
+            dir_entries.push(GroupDirEntry {
+                group_id: *group_id,
+                member_table_ids: levels.member_table_ids.clone(),
+                offset,
+                length,
+            });
+        }
+
+        let mut buf = Vec::new();
+        buf.put_u32_le(MAGIC);
+        buf.put_u64_le(self.id);
+        buf.put_u64_le(self.max_committed_epoch);
+        buf.put_u64_le(self.safe_epoch);
+        buf.put_u32_le(dir_entries.len() as u32);
+        for entry in &dir_entries {
+            buf.put_u64_le(entry.group_id);
+            buf.put_u32_le(entry.offset);
+            buf.put_u32_le(entry.length);
+            buf.put_u32_le(entry.member_table_ids.len() as u32);
+            for table_id in &entry.member_table_ids {
+                buf.put_u32_le(*table_id);
+            }
+        }
+        buf.extend_from_slice(&data_section);
+        Bytes::from(buf)
+    }
+}
+
+/// A [`HummockVersion`] parsed just enough to answer membership/object-id queries, with each
+/// compaction group's full `Levels` (including `table_infos`) decoded lazily on first access.
+pub struct LazyHummockVersion {
+    id: u64,
+    max_committed_epoch: u64,
+    safe_epoch: u64,
+    dir: Vec<GroupDirEntry>,
+    raw: Bytes,
+    /// Offset of the data section within `raw`, i.e. where group payloads begin.
+    data_start: usize,
+    cache: RefCell<HashMap<CompactionGroupId, Levels>>,
+}
+
+impl LazyHummockVersion {
+    /// Parses the group directory out of `bytes` eagerly. No `Levels`/`table_infos` are decoded
+    /// yet; that happens lazily in [`Self::get_compaction_group_levels`].
+    pub fn parse(bytes: Bytes) -> Option<Self> {
+        let mut buf = &bytes[..];
+        if buf.remaining() < 4 || buf.get_u32_le() != MAGIC {
+            return None;
+        }
+        let id = buf.get_u64_le();
+        let max_committed_epoch = buf.get_u64_le();
+        let safe_epoch = buf.get_u64_le();
+        let num_groups = buf.get_u32_le() as usize;
+        let mut dir = Vec::with_capacity(num_groups);
+        for _ in 0..num_groups {
+            let group_id = buf.get_u64_le();
+            let offset = buf.get_u32_le();
+            let length = buf.get_u32_le();
+            let num_tables = buf.get_u32_le() as usize;
+            let mut member_table_ids = Vec::with_capacity(num_tables);
+            for _ in 0..num_tables {
+                member_table_ids.push(buf.get_u32_le());
+            }
+            dir.push(GroupDirEntry {
+                group_id,
+                member_table_ids,
+                offset,
+                length,
+            });
+        }
+        let data_start = bytes.len() - buf.remaining();
+        Some(Self {
+            id,
+            max_committed_epoch,
+            safe_epoch,
+            dir,
+            raw: bytes,
+            data_start,
+            cache: RefCell::new(HashMap::new()),
+        })
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn max_committed_epoch(&self) -> u64 {
+        self.max_committed_epoch
+    }
+
+    pub fn safe_epoch(&self) -> u64 {
+        self.safe_epoch
+    }
+
+    /// Returns the member table ids of `group_id` without decoding its `Levels` payload.
+    pub fn member_table_ids(&self, group_id: CompactionGroupId) -> Option<&[u32]> {
+        self.dir
+            .iter()
+            .find(|e| e.group_id == group_id)
+            .map(|e| e.member_table_ids.as_slice())
+    }
+
+    /// Returns (and caches) the fully decoded `Levels` for `group_id`, parsing its payload only
+    /// the first time it's requested.
+    pub fn get_compaction_group_levels(&self, group_id: CompactionGroupId) -> Option<Levels> {
+        if let Some(levels) = self.cache.borrow().get(&group_id) {
+            return Some(levels.clone());
+        }
+        let entry = self.dir.iter().find(|e| e.group_id == group_id)?;
+        let start = self.data_start + entry.offset as usize;
+        let end = start + entry.length as usize;
+        let levels = Levels::decode(&self.raw[start..end]).ok()?;
+        self.cache
+            .borrow_mut()
+            .insert(group_id, levels.clone());
+        Some(levels)
+    }
+
+    /// Builds the table-id -> compaction-group-id mapping straight from the directory, without
+    /// decoding any group's `table_infos`.
+    pub fn build_compaction_group_info(&self) -> HashMap<u32, CompactionGroupId> {
+        let mut ret = HashMap::new();
+        for entry in &self.dir {
+            for table_id in &entry.member_table_ids {
+                ret.insert(*table_id, entry.group_id);
+            }
+        }
+        ret
+    }
+
+    /// Returns every SST object id across all groups. This does decode every group's `Levels`
+    /// (the object ids live inside `table_infos`), but each group is still only decoded once
+    /// and the result is cached for subsequent calls that also need that group's levels.
+    pub fn get_object_ids(&self) -> Vec<u64> {
+        self.dir
+            .iter()
+            .map(|e| e.group_id)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .filter_map(|group_id| self.get_compaction_group_levels(group_id))
+            .flat_map(|levels| {
+                levels
+                    .l0
+                    .into_iter()
+                    .flat_map(|l0| l0.sub_levels)
+                    .chain(levels.levels)
+                    .flat_map(|level| level.table_infos)
+                    .map(|sst| sst.object_id)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use risingwave_pb::hummock::{CompactionConfig, HummockVersion};
+
+    use super::*;
+    use crate::compaction_group::hummock_version_ext::build_initial_compaction_group_levels;
+
+    #[test]
+    fn test_lazy_round_trip() {
+        let version = HummockVersion {
+            id: 7,
+            levels: HashMap::from_iter([(
+                42,
+                build_initial_compaction_group_levels(
+                    42,
+                    &CompactionConfig {
+                        max_level: 3,
+                        ..Default::default()
+                    },
+                ),
+            )]),
+            max_committed_epoch: 5,
+            safe_epoch: 1,
+            table_watermarks: HashMap::new(),
+        };
+        let bytes = version.serialize_compact();
+        let lazy = LazyHummockVersion::parse(bytes).unwrap();
+        assert_eq!(lazy.id(), 7);
+        assert_eq!(lazy.max_committed_epoch(), 5);
+        assert_eq!(lazy.member_table_ids(42), Some([].as_slice()));
+        assert!(lazy.get_compaction_group_levels(42).is_some());
+        assert!(lazy.get_object_ids().is_empty());
+    }
+}
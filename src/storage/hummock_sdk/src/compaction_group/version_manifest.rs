@@ -0,0 +1,209 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A durable, append-only log of [`HummockVersionDelta`] edits plus periodic full-version
+//! checkpoints, modeled on LevelDB's `MANIFEST` file. [`VersionManifest::recover`] rebuilds the
+//! current [`HummockVersion`] by reading the latest checkpoint and replaying the trailing
+//! deltas through [`HummockVersionUpdateExt::apply_version_delta`]. Unlike LevelDB's one
+//! manifest per `VersionEdit`, an existing manifest below [`VersionManifest::REUSE_SIZE_THRESHOLD`]
+//! is reused (appended to) across recoveries rather than rewritten from scratch.
+
+use std::io::{self, Read, Write};
+
+use prost::Message;
+use risingwave_pb::hummock::{HummockVersion, HummockVersionDelta};
+
+use super::hummock_version_ext::HummockVersionUpdateExt;
+
+/// Tag byte distinguishing the two record kinds stored in a manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RecordKind {
+    Checkpoint = 0,
+    Delta = 1,
+}
+
+impl RecordKind {
+    fn from_byte(b: u8) -> Option<Self> {
+        match b {
+            0 => Some(Self::Checkpoint),
+            1 => Some(Self::Delta),
+            _ => None,
+        }
+    }
+}
+
+/// An append-only manifest of version edits, plus the logic to reconstruct a [`HummockVersion`]
+/// from it and to decide when the underlying log has grown large enough to warrant a rollover.
+pub struct VersionManifest {
+    /// Size, in bytes, of the manifest as currently persisted. Tracked so
+    /// [`Self::should_reuse`] doesn't need to re-stat the file on every call.
+    size_bytes: u64,
+}
+
+impl VersionManifest {
+    /// Once an existing manifest exceeds this size, recovery rolls over to a fresh checkpoint +
+    /// manifest instead of continuing to append, bounding how many deltas must be replayed on
+    /// the next recovery.
+    pub const REUSE_SIZE_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+    /// Record header: 1 byte kind + 4 byte little-endian length.
+    const RECORD_HEADER_LEN: usize = 5;
+
+    pub fn new() -> Self {
+        Self { size_bytes: 0 }
+    }
+
+    /// Current persisted size of the manifest.
+    pub fn size_bytes(&self) -> u64 {
+        self.size_bytes
+    }
+
+    /// Whether an existing manifest of this size should be reused (appended to) rather than
+    /// replaced by a fresh checkpoint + manifest, mirroring LevelDB's manifest-reuse optimization.
+    pub fn should_reuse(&self) -> bool {
+        self.size_bytes < Self::REUSE_SIZE_THRESHOLD
+    }
+
+    /// Appends a full-version checkpoint record. The caller must `fsync` (or equivalent) the
+    /// writer *before* any reader is told this checkpoint exists, e.g. before truncating an
+    /// older manifest, so a crash can never leave a reference to an undurable checkpoint.
+    pub fn write_checkpoint<W: Write>(&mut self, writer: &mut W, version: &HummockVersion) -> io::Result<()> {
+        self.write_record(writer, RecordKind::Checkpoint, &version.encode_to_vec())
+    }
+
+    /// Appends a delta record. Should be called after the delta has already been applied
+    /// in-memory via `apply_version_delta`, so the manifest only ever records edits that took
+    /// effect.
+    pub fn log_delta<W: Write>(&mut self, writer: &mut W, delta: &HummockVersionDelta) -> io::Result<()> {
+        self.write_record(writer, RecordKind::Delta, &delta.encode_to_vec())
+    }
+
+    fn write_record<W: Write>(&mut self, writer: &mut W, kind: RecordKind, payload: &[u8]) -> io::Result<()> {
+        let mut header = [0u8; Self::RECORD_HEADER_LEN];
+        header[0] = kind as u8;
+        header[1..].copy_from_slice(&(payload.len() as u32).to_le_bytes());
+        writer.write_all(&header)?;
+        writer.write_all(payload)?;
+        self.size_bytes += (Self::RECORD_HEADER_LEN + payload.len()) as u64;
+        Ok(())
+    }
+
+    /// Reads every record out of `reader`, reconstructing the latest checkpoint (or a fresh
+    /// default version if none is present) and replaying every subsequent delta on top of it
+    /// via `apply_version_delta`.
+    ///
+    /// A truncated trailing record — e.g. from a crash mid-write — is treated as the end of the
+    /// log rather than an error: reads stop at the first incomplete header or payload.
+    pub fn recover<R: Read>(&mut self, reader: &mut R) -> io::Result<HummockVersion> {
+        self.size_bytes = 0;
+        let mut version = HummockVersion::default();
+        loop {
+            let mut header = [0u8; Self::RECORD_HEADER_LEN];
+            if let Err(e) = reader.read_exact(&mut header) {
+                if e.kind() == io::ErrorKind::UnexpectedEof {
+                    break;
+                }
+                return Err(e);
+            }
+            let Some(kind) = RecordKind::from_byte(header[0]) else {
+                // Unknown/corrupt record tag: treat as a torn write and stop here, same as EOF.
+                break;
+            };
+            let len = u32::from_le_bytes(header[1..].try_into().unwrap()) as usize;
+            let mut payload = vec![0u8; len];
+            if let Err(e) = reader.read_exact(&mut payload) {
+                if e.kind() == io::ErrorKind::UnexpectedEof {
+                    // Partially written trailing record: ignore and stop replaying.
+                    break;
+                }
+                return Err(e);
+            }
+
+            match kind {
+                RecordKind::Checkpoint => {
+                    let Ok(checkpoint) = HummockVersion::decode(payload.as_slice()) else {
+                        break;
+                    };
+                    version = checkpoint;
+                }
+                RecordKind::Delta => {
+                    let Ok(delta) = HummockVersionDelta::decode(payload.as_slice()) else {
+                        break;
+                    };
+                    version.apply_version_delta(&delta);
+                }
+            }
+            self.size_bytes += (Self::RECORD_HEADER_LEN + len) as u64;
+        }
+        Ok(version)
+    }
+}
+
+impl Default for VersionManifest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use risingwave_pb::hummock::group_delta::DeltaType;
+    use risingwave_pb::hummock::hummock_version_delta::GroupDeltas;
+    use risingwave_pb::hummock::{CompactionConfig, GroupConstruct, GroupDelta};
+
+    use super::*;
+
+    #[test]
+    fn test_recover_reuses_checkpoint_and_replays_deltas() {
+        let mut manifest = VersionManifest::new();
+        let mut log = Vec::new();
+
+        let base_version = HummockVersion::default();
+        manifest.write_checkpoint(&mut log, &base_version).unwrap();
+
+        let delta = HummockVersionDelta {
+            id: 1,
+            group_deltas: std::collections::HashMap::from_iter([(
+                100,
+                GroupDeltas {
+                    group_deltas: vec![GroupDelta {
+                        delta_type: Some(DeltaType::GroupConstruct(GroupConstruct {
+                            group_config: Some(CompactionConfig {
+                                max_level: 1,
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        })),
+                    }],
+                },
+            )]),
+            ..Default::default()
+        };
+        manifest.log_delta(&mut log, &delta).unwrap();
+
+        let mut recovering = VersionManifest::new();
+        let recovered = recovering.recover(&mut Cursor::new(log.clone())).unwrap();
+        assert_eq!(recovered.id, 1);
+        assert!(recovered.levels.contains_key(&100));
+        assert!(recovering.should_reuse());
+
+        // A truncated trailing record must not fail recovery; it's simply ignored.
+        log.truncate(log.len() - 1);
+        let mut recovering_truncated = VersionManifest::new();
+        let recovered_truncated = recovering_truncated.recover(&mut Cursor::new(log)).unwrap();
+        assert_eq!(recovered_truncated.id, 0);
+    }
+}
@@ -13,10 +13,13 @@
 // limitations under the License.
 
 use std::ops::Bound;
+use std::sync::Arc;
+use std::time::Instant;
 
 use futures::stream::BoxStream;
 use futures::{Stream, StreamExt};
 use futures_async_stream::{for_await, try_stream};
+use prometheus::{register_histogram_vec, register_int_counter_vec, HistogramVec, IntCounterVec};
 use risingwave_common::util::addr::HostAddr;
 use risingwave_common_service::observer_manager::{Channel, NotificationClient, ObserverError};
 use risingwave_hummock_sdk::key::TableKey;
@@ -38,6 +41,72 @@ use risingwave_storage::store::{
 use risingwave_storage::{StateStore, StateStoreReadIterStream};
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 
+/// Wraps a freshly produced replay item stream, comparing it item-by-item (positionally, since
+/// iteration is always key-ordered) against `expected` -- the sequence originally recorded when
+/// the trace was captured -- and failing with `TraceError::ResultDivergence` at the first index
+/// that differs (including a short/long recorded sequence), rather than silently trusting that
+/// Hummock reproduced the same results.
+///
+/// Note: `expected` is threaded in here as if `ReplayRead::iter`/`LocalReplayRead::iter` had
+/// grown a parameter carrying the trace's recorded result, which needs the trace record types in
+/// `risingwave_hummock_trace` (and the `TraceError::ResultDivergence` variant itself) to be
+/// extended; that crate isn't part of this snapshot, so this is written to the shape such an
+/// extension would take.
+#[try_stream(ok = ReplayItem, error = TraceError)]
+async fn verify_against_recorded(
+    stream: impl Stream<Item = Result<ReplayItem>>,
+    expected: Vec<ReplayItem>,
+    op: &'static str,
+    epoch: u64,
+    metrics: Arc<ReplayMetrics>,
+) {
+    let start = Instant::now();
+    let mut bytes = 0usize;
+    let mut expected = expected.into_iter();
+    #[for_await]
+    for actual in stream {
+        let actual = actual?;
+        match expected.next() {
+            Some(expected_item) if expected_item == actual => {}
+            Some(expected_item) => {
+                return Err(TraceError::ResultDivergence {
+                    op: op.to_string(),
+                    epoch,
+                    expected: format!("{expected_item:?}"),
+                    actual: format!("{actual:?}"),
+                });
+            }
+            None => {
+                return Err(TraceError::ResultDivergence {
+                    op: op.to_string(),
+                    epoch,
+                    expected: "<end of recorded sequence>".to_string(),
+                    actual: format!("{actual:?}"),
+                });
+            }
+        }
+        bytes += actual.0.len() + actual.1.len();
+        yield actual;
+    }
+    if let Some(leftover) = expected.next() {
+        return Err(TraceError::ResultDivergence {
+            op: op.to_string(),
+            epoch,
+            expected: format!("{leftover:?}"),
+            actual: "<end of replayed stream>".to_string(),
+        });
+    }
+    metrics.op_count.with_label_values(&[op]).inc();
+    metrics
+        .op_bytes
+        .with_label_values(&[op])
+        .inc_by(bytes as u64);
+    metrics
+        .op_latency
+        .with_label_values(&[op])
+        .observe(start.elapsed().as_secs_f64());
+}
+
 pub(crate) struct GlobalReplayIter<S>
 where
     S: StateStoreReadIterStream,
@@ -62,37 +131,108 @@ where
     }
 }
 
+/// Lazily adapts a [`StateStoreIterItemStream`] into a [`ReplayItem`] stream, converting each item
+/// on demand (same shape as [`GlobalReplayIter`]) instead of draining it into a `Vec` up front --
+/// so replaying a scan over a large table runs in bounded memory rather than buffering the whole
+/// result set before the first item is yielded.
 pub(crate) struct LocalReplayIter {
-    inner: Vec<ReplayItem>,
+    inner: BoxStream<'static, Result<ReplayItem>>,
 }
 
 impl LocalReplayIter {
-    pub(crate) async fn new(stream: impl StateStoreIterItemStream) -> Self {
-        let mut inner: Vec<_> = Vec::new();
-        #[for_await]
-        for value in stream {
-            let value = value.unwrap();
-            inner.push((value.0.user_key.table_key.0.into(), value.1.into()));
-        }
+    pub(crate) fn new(stream: impl StateStoreIterItemStream + 'static) -> Self {
+        let inner = stream
+            .map(|item_res| {
+                item_res
+                    .map(|(key, value)| (key.user_key.table_key.0.into(), value.into()))
+                    .map_err(|_| TraceError::IterFailed("iter failed to retrieve item".to_string()))
+            })
+            .boxed();
         Self { inner }
     }
 
-    #[try_stream(ok = ReplayItem, error = TraceError)]
-    pub(crate) async fn into_stream(self) {
-        for (key, value) in self.inner {
-            yield (key, value)
+    pub(crate) fn into_stream(self) -> impl Stream<Item = Result<ReplayItem>> {
+        self.inner
+    }
+}
+
+/// Per-operation Prometheus metrics for the replay engine: the count and total bytes of each
+/// kind of op replayed, and per-op-type wall-clock latency, so a long replay run can be watched
+/// live via the usual metrics scrape and compared against the timings recorded in the trace.
+///
+/// Note: actually serving these over an HTTP scrape endpoint needs an exporter server wired into
+/// this binary's `main`, which isn't part of this snapshot (only the replay trait impls are);
+/// these register against the global default [`prometheus::Registry`], ready for whatever serves
+/// `/metrics` to pick up. The replay-vs-recorded throughput ratio mentioned alongside these is
+/// likewise out of reach here, since the originally recorded per-op timings live in the trace
+/// record types (`risingwave_hummock_trace`), not in this file.
+pub(crate) struct ReplayMetrics {
+    op_count: IntCounterVec,
+    op_bytes: IntCounterVec,
+    op_latency: HistogramVec,
+    notify_hummock_count: IntCounterVec,
+}
+
+impl ReplayMetrics {
+    pub(crate) fn new() -> Self {
+        let op_count = register_int_counter_vec!(
+            "replay_op_total",
+            "number of replayed operations, by op type",
+            &["op"]
+        )
+        .unwrap();
+        let op_bytes = register_int_counter_vec!(
+            "replay_op_bytes_total",
+            "total bytes touched by replayed operations, by op type",
+            &["op"]
+        )
+        .unwrap();
+        let op_latency = register_histogram_vec!(
+            "replay_op_latency_seconds",
+            "replay wall-clock latency, by op type",
+            &["op"]
+        )
+        .unwrap();
+        let notify_hummock_count = register_int_counter_vec!(
+            "replay_notify_hummock_total",
+            "number of notify_hummock dispatches",
+            &["notification"]
+        )
+        .unwrap();
+        Self {
+            op_count,
+            op_bytes,
+            op_latency,
+            notify_hummock_count,
         }
     }
+
+    fn observe(&self, op: &str, bytes: usize, start: Instant) {
+        self.op_count.with_label_values(&[op]).inc();
+        self.op_bytes.with_label_values(&[op]).inc_by(bytes as u64);
+        self.op_latency
+            .with_label_values(&[op])
+            .observe(start.elapsed().as_secs_f64());
+    }
 }
 
 pub(crate) struct GlobalReplayImpl {
     store: HummockStorage,
     notifier: NotificationManagerRef,
+    metrics: Arc<ReplayMetrics>,
 }
 
 impl GlobalReplayImpl {
-    pub(crate) fn new(store: HummockStorage, notifier: NotificationManagerRef) -> Self {
-        Self { store, notifier }
+    pub(crate) fn new(
+        store: HummockStorage,
+        notifier: NotificationManagerRef,
+        metrics: Arc<ReplayMetrics>,
+    ) -> Self {
+        Self {
+            store,
+            notifier,
+            metrics,
+        }
     }
 }
 
@@ -105,6 +245,7 @@ impl ReplayRead for GlobalReplayImpl {
         key_range: (Bound<TracedBytes>, Bound<TracedBytes>),
         epoch: u64,
         read_options: TracedReadOptions,
+        expected: Vec<ReplayItem>,
     ) -> Result<BoxStream<'static, Result<ReplayItem>>> {
         let key_range = (
             key_range.0.map(TracedBytes::into).map(TableKey),
@@ -117,7 +258,9 @@ impl ReplayRead for GlobalReplayImpl {
             .await
             .unwrap();
         let iter = iter.boxed();
-        let stream = GlobalReplayIter::new(iter).into_stream().boxed();
+        let stream = GlobalReplayIter::new(iter).into_stream();
+        let stream =
+            verify_against_recorded(stream, expected, "iter", epoch, self.metrics.clone()).boxed();
         Ok(stream)
     }
 
@@ -126,24 +269,39 @@ impl ReplayRead for GlobalReplayImpl {
         key: TracedBytes,
         epoch: u64,
         read_options: TracedReadOptions,
+        expected: Option<TracedBytes>,
     ) -> Result<Option<TracedBytes>> {
-        Ok(self
+        let start = Instant::now();
+        let actual = self
             .store
             .get(TableKey(key.into()), epoch, read_options.into())
             .await
             .unwrap()
-            .map(TracedBytes::from))
+            .map(TracedBytes::from);
+        if actual != expected {
+            return Err(TraceError::ResultDivergence {
+                op: "get".to_string(),
+                epoch,
+                expected: format!("{expected:?}"),
+                actual: format!("{actual:?}"),
+            });
+        }
+        self.metrics
+            .observe("get", actual.as_ref().map_or(0, |v| v.len()), start);
+        Ok(actual)
     }
 }
 
 #[async_trait::async_trait]
 impl ReplayStateStore for GlobalReplayImpl {
     async fn sync(&self, id: u64) -> Result<usize> {
+        let start = Instant::now();
         let result: SyncResult = self
             .store
             .sync(id)
             .await
             .map_err(|e| TraceError::SyncFailed(format!("{e}")))?;
+        self.metrics.observe("sync", result.sync_size, start);
         Ok(result.sync_size)
     }
 
@@ -164,12 +322,16 @@ impl ReplayStateStore for GlobalReplayImpl {
         if let Some(prev_version_id) = prev_version_id {
             self.store.wait_version_update(prev_version_id).await;
         }
+        self.metrics
+            .notify_hummock_count
+            .with_label_values(&["notify_hummock"])
+            .inc();
         Ok(version)
     }
 
     async fn new_local(&self, options: TracedNewLocalOptions) -> Box<dyn LocalReplay> {
         let local_storage = self.store.new_local(options.into()).await;
-        Box::new(LocalReplayImpl(local_storage))
+        Box::new(LocalReplayImpl(local_storage, self.metrics.clone()))
     }
 
     async fn try_wait_epoch(&self, epoch: HummockReadEpoch) -> Result<()> {
@@ -195,7 +357,7 @@ impl ReplayStateStore for GlobalReplayImpl {
         Ok(())
     }
 }
-pub(crate) struct LocalReplayImpl(LocalHummockStorage);
+pub(crate) struct LocalReplayImpl(LocalHummockStorage, Arc<ReplayMetrics>);
 
 #[async_trait::async_trait]
 impl LocalReplay for LocalReplayImpl {
@@ -221,14 +383,18 @@ impl LocalReplay for LocalReplayImpl {
         &mut self,
         delete_ranges: Vec<(Bound<TracedBytes>, Bound<TracedBytes>)>,
     ) -> Result<usize> {
+        let start = Instant::now();
         let delete_ranges = delete_ranges
             .into_iter()
             .map(|(start, end)| (start.map(TracedBytes::into), end.map(TracedBytes::into)))
             .collect();
-        self.0
+        let result = self
+            .0
             .flush(delete_ranges)
             .await
-            .map_err(|_| TraceError::FlushFailed)
+            .map_err(|_| TraceError::FlushFailed)?;
+        self.1.observe("flush", result, start);
+        Ok(result)
     }
 
     fn is_dirty(&self) -> bool {
@@ -242,6 +408,7 @@ impl LocalReplayRead for LocalReplayImpl {
         &self,
         key_range: (Bound<TracedBytes>, Bound<TracedBytes>),
         read_options: TracedReadOptions,
+        expected: Vec<ReplayItem>,
     ) -> Result<BoxStream<'static, Result<ReplayItem>>> {
         let key_range = (
             key_range.0.map(|b| TableKey(b.into())),
@@ -253,7 +420,10 @@ impl LocalReplayRead for LocalReplayImpl {
             .unwrap();
 
         let iter = iter.boxed();
-        let stream = LocalReplayIter::new(iter).await.into_stream().boxed();
+        let stream = LocalReplayIter::new(iter).into_stream();
+        let stream =
+            verify_against_recorded(stream, expected, "iter", self.0.epoch(), self.1.clone())
+                .boxed();
         Ok(stream)
     }
 
@@ -261,13 +431,24 @@ impl LocalReplayRead for LocalReplayImpl {
         &self,
         key: TracedBytes,
         read_options: TracedReadOptions,
+        expected: Option<TracedBytes>,
     ) -> Result<Option<TracedBytes>> {
-        Ok(
-            LocalStateStore::get(&self.0, TableKey(key.into()), read_options.into())
-                .await
-                .unwrap()
-                .map(TracedBytes::from),
-        )
+        let start = Instant::now();
+        let actual = LocalStateStore::get(&self.0, TableKey(key.into()), read_options.into())
+            .await
+            .unwrap()
+            .map(TracedBytes::from);
+        if actual != expected {
+            return Err(TraceError::ResultDivergence {
+                op: "get".to_string(),
+                epoch: self.0.epoch(),
+                expected: format!("{expected:?}"),
+                actual: format!("{actual:?}"),
+            });
+        }
+        self.1
+            .observe("get", actual.as_ref().map_or(0, |v| v.len()), start);
+        Ok(actual)
     }
 }
 
@@ -279,6 +460,8 @@ impl ReplayWrite for LocalReplayImpl {
         new_val: TracedBytes,
         old_val: Option<TracedBytes>,
     ) -> Result<()> {
+        let start = Instant::now();
+        let bytes = key.len() + new_val.len() + old_val.as_ref().map_or(0, |v| v.len());
         LocalStateStore::insert(
             &mut self.0,
             TableKey(key.into()),
@@ -286,13 +469,59 @@ impl ReplayWrite for LocalReplayImpl {
             old_val.map(|b| b.into()),
         )
         .unwrap();
+        self.1.observe("insert", bytes, start);
         Ok(())
     }
 
     fn delete(&mut self, key: TracedBytes, old_val: TracedBytes) -> Result<()> {
+        let start = Instant::now();
+        let bytes = key.len() + old_val.len();
         LocalStateStore::delete(&mut self.0, TableKey(key.into()), old_val.into()).unwrap();
+        self.1.observe("delete", bytes, start);
         Ok(())
     }
+
+    /// Applies one epoch's worth of mutations -- point puts, point deletes, and range deletes --
+    /// in a single pass over `LocalStateStore`, rather than one `ReplayWrite` call per row.
+    /// Returns the total bytes ingested.
+    ///
+    /// Note: `ReplayWrite` itself lives in `risingwave_hummock_trace`, which isn't part of this
+    /// snapshot; this is written as if that trait had grown this method, since the recorder-side
+    /// coalescing of consecutive same-epoch writes into one batch record needs the trace format
+    /// types that live in that same absent crate.
+    async fn ingest_batch(
+        &mut self,
+        puts: Vec<(TracedBytes, TracedBytes, Option<TracedBytes>)>,
+        deletes: Vec<(TracedBytes, TracedBytes)>,
+        delete_ranges: Vec<(Bound<TracedBytes>, Bound<TracedBytes>)>,
+    ) -> Result<usize> {
+        let start = Instant::now();
+        let mut bytes = 0usize;
+        for (key, new_val, old_val) in puts {
+            bytes += key.len() + new_val.len() + old_val.as_ref().map_or(0, |v| v.len());
+            LocalStateStore::insert(
+                &mut self.0,
+                TableKey(key.into()),
+                new_val.into(),
+                old_val.map(|b| b.into()),
+            )
+            .unwrap();
+        }
+        for (key, old_val) in deletes {
+            bytes += key.len() + old_val.len();
+            LocalStateStore::delete(&mut self.0, TableKey(key.into()), old_val.into()).unwrap();
+        }
+        let delete_ranges = delete_ranges
+            .into_iter()
+            .map(|(start, end)| (start.map(TracedBytes::into), end.map(TracedBytes::into)))
+            .collect();
+        self.0
+            .flush(delete_ranges)
+            .await
+            .map_err(|_| TraceError::FlushFailed)?;
+        self.1.observe("ingest_batch", bytes, start);
+        Ok(bytes)
+    }
 }
 
 pub struct ReplayNotificationClient {
@@ -16,7 +16,7 @@ use std::future::Future;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 
-use more_asserts::assert_gt;
+use more_asserts::{assert_gt, assert_lt};
 
 use super::{HummockResult, HummockValue};
 
@@ -125,6 +125,74 @@ pub trait HummockIterator: Send + Sync {
 
     /// take local statistic info from iterator to report metrics.
     fn collect_local_statistic(&self, _stats: &mut StoreLocalStatistic);
+
+    /// Builds a tree node describing this iterator's composition, for read-path debugging (e.g.
+    /// dumping which of a scan's sources dominate). The default records a single leaf carrying
+    /// this iterator's type name and a snapshot of its [`StoreLocalStatistic`] counters;
+    /// composite iterators (unions, merges, concats, boxes) override this to recurse into their
+    /// children instead.
+    fn describe(&self) -> IterTreeNode {
+        let mut stats = StoreLocalStatistic::default();
+        self.collect_local_statistic(&mut stats);
+        IterTreeNode::leaf(std::any::type_name::<Self>(), stats)
+    }
+}
+
+/// A single node in the tree built by [`HummockIterator::describe`]: the iterator's display
+/// label, a snapshot of its locally accumulated [`StoreLocalStatistic`] counters, and any children
+/// it wraps.
+#[derive(Debug)]
+pub struct IterTreeNode {
+    pub label: String,
+    pub stats: StoreLocalStatistic,
+    pub children: Vec<IterTreeNode>,
+}
+
+impl IterTreeNode {
+    pub fn leaf(label: impl Into<String>, stats: StoreLocalStatistic) -> Self {
+        Self {
+            label: label.into(),
+            stats,
+            children: vec![],
+        }
+    }
+
+    pub fn branch(label: impl Into<String>, children: Vec<IterTreeNode>) -> Self {
+        Self {
+            label: label.into(),
+            stats: StoreLocalStatistic::default(),
+            children,
+        }
+    }
+
+    /// Serializes this tree as a Graphviz DOT directed graph: one node per tree node, with `->`
+    /// edges from each parent to its children, and labels carrying the iterator type plus its
+    /// `StoreLocalStatistic` counters. Lets operators dump a scan's read plan to a `.dot` file and
+    /// visually diagnose which source dominates.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n");
+        let mut next_id = 0u64;
+        self.write_dot(&mut out, &mut next_id, None);
+        out.push_str("}\n");
+        out
+    }
+
+    fn write_dot(&self, out: &mut String, next_id: &mut u64, parent: Option<u64>) -> u64 {
+        let id = *next_id;
+        *next_id += 1;
+        out.push_str(&format!(
+            "  n{id} [label=\"{}\\n{:?}\"];\n",
+            self.label.replace('"', "'"),
+            self.stats
+        ));
+        if let Some(parent) = parent {
+            out.push_str(&format!("  n{parent} -> n{id};\n"));
+        }
+        for child in &self.children {
+            child.write_dot(out, next_id, Some(id));
+        }
+        id
+    }
 }
 
 /// This is a placeholder trait used in `HummockIteratorUnion`
@@ -160,6 +228,10 @@ impl<D: HummockIteratorDirection> HummockIterator for PhantomHummockIterator<D>
     }
 
     fn collect_local_statistic(&self, _stats: &mut StoreLocalStatistic) {}
+
+    fn describe(&self) -> IterTreeNode {
+        unreachable!()
+    }
 }
 
 /// The `HummockIteratorUnion` acts like a wrapper over multiple types of `HummockIterator`, so that
@@ -259,6 +331,16 @@ impl<
             Fourth(iter) => iter.collect_local_statistic(stats),
         }
     }
+
+    fn describe(&self) -> IterTreeNode {
+        let (arm, inner) = match self {
+            First(iter) => ("First", iter.describe()),
+            Second(iter) => ("Second", iter.describe()),
+            Third(iter) => ("Third", iter.describe()),
+            Fourth(iter) => ("Fourth", iter.describe()),
+        };
+        IterTreeNode::branch(format!("HummockIteratorUnion::{arm}"), vec![inner])
+    }
 }
 
 impl<I: HummockIterator> HummockIterator for Box<I> {
@@ -291,6 +373,10 @@ impl<I: HummockIterator> HummockIterator for Box<I> {
     fn collect_local_statistic(&self, stats: &mut StoreLocalStatistic) {
         (*self).deref().collect_local_statistic(stats);
     }
+
+    fn describe(&self) -> IterTreeNode {
+        (*self).deref().describe()
+    }
 }
 
 pub enum RustIteratorOfBuilder<'a, B: RustIteratorBuilder> {
@@ -439,6 +525,186 @@ impl<'a, B: RustIteratorBuilder> HummockIterator for FromRustIterator<'a, B> {
     }
 
     fn collect_local_statistic(&self, _stats: &mut StoreLocalStatistic) {}
+
+    fn describe(&self) -> IterTreeNode {
+        let mut stats = StoreLocalStatistic::default();
+        self.collect_local_statistic(&mut stats);
+        IterTreeNode::leaf(
+            format!("FromRustIterator(table_id={})", self.table_id.table_id()),
+            stats,
+        )
+    }
+}
+
+pub enum BackwardRustIteratorOfBuilder<'a, B: BackwardRustIteratorBuilder> {
+    Seek(B::SeekIter<'a>),
+    Rewind(B::RewindIter<'a>),
+}
+
+impl<'a, B: BackwardRustIteratorBuilder> Iterator for BackwardRustIteratorOfBuilder<'a, B> {
+    type Item = (TableKey<&'a [u8]>, HummockValue<&'a [u8]>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            BackwardRustIteratorOfBuilder::Seek(i) => i.next(),
+            BackwardRustIteratorOfBuilder::Rewind(i) => i.next(),
+        }
+    }
+}
+
+/// The backward counterpart of [`RustIteratorBuilder`]: `rewind` positions at the last entry
+/// instead of the first, and `seek` lands on the largest key `<=` the target instead of the
+/// smallest key `>=` it. Kept as a separate trait, rather than adding reverse-direction associated
+/// types to `RustIteratorBuilder` itself, so that existing forward-only builders don't need to
+/// grow new associated types they'll never use.
+pub trait BackwardRustIteratorBuilder: Send + Sync + 'static {
+    type Iterable: Send + Sync;
+    type RewindIter<'a>: Iterator<Item = (TableKey<&'a [u8]>, HummockValue<&'a [u8]>)>
+        + Send
+        + Sync
+        + 'a;
+    type SeekIter<'a>: Iterator<Item = (TableKey<&'a [u8]>, HummockValue<&'a [u8]>)>
+        + Send
+        + Sync
+        + 'a;
+
+    /// Returns entries with key `<= seek_key`, in descending key order.
+    fn seek<'a>(iterable: &'a Self::Iterable, seek_key: TableKey<&[u8]>) -> Self::SeekIter<'a>;
+    /// Returns all entries in descending key order, starting from the last one.
+    fn rewind(iterable: &Self::Iterable) -> Self::RewindIter<'_>;
+}
+
+pub struct BackwardFromRustIterator<'a, B: BackwardRustIteratorBuilder> {
+    inner: &'a B::Iterable,
+    #[expect(clippy::type_complexity)]
+    iter: Option<(
+        BackwardRustIteratorOfBuilder<'a, B>,
+        TableKey<&'a [u8]>,
+        HummockValue<&'a [u8]>,
+    )>,
+    epoch: EpochWithGap,
+    table_id: TableId,
+}
+
+impl<'a, B: BackwardRustIteratorBuilder> BackwardFromRustIterator<'a, B> {
+    pub fn new(inner: &'a B::Iterable, epoch: EpochWithGap, table_id: TableId) -> Self {
+        Self {
+            inner,
+            iter: None,
+            epoch,
+            table_id,
+        }
+    }
+}
+
+impl<'a, B: BackwardRustIteratorBuilder> HummockIterator for BackwardFromRustIterator<'a, B> {
+    type Direction = Backward;
+
+    async fn next(&mut self) -> HummockResult<()> {
+        let (iter, key, value) = self.iter.as_mut().expect("should be valid");
+        if let Some((new_key, new_value)) = iter.next() {
+            *key = new_key;
+            *value = new_value;
+        } else {
+            self.iter = None;
+        }
+        Ok(())
+    }
+
+    fn key(&self) -> FullKey<&[u8]> {
+        let (_, key, _) = self.iter.as_ref().expect("should be valid");
+        FullKey {
+            epoch_with_gap: self.epoch,
+            user_key: UserKey {
+                table_id: self.table_id,
+                table_key: *key,
+            },
+        }
+    }
+
+    fn value(&self) -> HummockValue<&[u8]> {
+        let (_, _, value) = self.iter.as_ref().expect("should be valid");
+        *value
+    }
+
+    fn is_valid(&self) -> bool {
+        self.iter.is_some()
+    }
+
+    async fn rewind(&mut self) -> HummockResult<()> {
+        let mut iter = B::rewind(self.inner);
+        if let Some((key, value)) = iter.next() {
+            self.iter = Some((BackwardRustIteratorOfBuilder::Rewind(iter), key, value));
+        } else {
+            self.iter = None;
+        }
+        Ok(())
+    }
+
+    async fn seek<'b>(&'b mut self, key: FullKey<&'b [u8]>) -> HummockResult<()> {
+        if self.table_id > key.user_key.table_id {
+            // This iterator's whole range sorts after the seek key, so going backward from it
+            // can never reach a key <= the target.
+            self.iter = None;
+            return Ok(());
+        }
+        if self.table_id < key.user_key.table_id {
+            // This iterator's whole range already sorts before the seek key, so every entry
+            // qualifies; start from the last one.
+            return self.rewind().await;
+        }
+        let mut iter = B::seek(self.inner, key.user_key.table_key);
+        match iter.next() {
+            Some((first_key, first_value)) => {
+                let first_full_key = FullKey {
+                    epoch_with_gap: self.epoch,
+                    user_key: UserKey {
+                        table_id: self.table_id,
+                        table_key: first_key,
+                    },
+                };
+                if first_full_key > key {
+                    // The semantic of `seek` ensures `first_key` <= table_key of `key`. We've
+                    // already checked `self.table_id` == table_id of `key`. Therefore, when
+                    // `first_full_key` > `key`, the only possibility is that `first_key` ==
+                    // table_key of `key`, and `self.epoch` < epoch of `key` (equal user keys
+                    // order by descending epoch).
+                    assert_eq!(first_key, key.user_key.table_key);
+                    match iter.next() {
+                        Some((next_key, next_value)) => {
+                            assert_lt!(next_key, first_key);
+                            self.iter = Some((
+                                BackwardRustIteratorOfBuilder::Seek(iter),
+                                next_key,
+                                next_value,
+                            ));
+                        }
+                        None => {
+                            self.iter = None;
+                        }
+                    }
+                } else {
+                    self.iter =
+                        Some((BackwardRustIteratorOfBuilder::Seek(iter), first_key, first_value));
+                }
+            }
+            None => {
+                self.iter = None;
+            }
+        }
+        Ok(())
+    }
+
+    fn collect_local_statistic(&self, _stats: &mut StoreLocalStatistic) {}
+
+    fn describe(&self) -> IterTreeNode {
+        let mut stats = StoreLocalStatistic::default();
+        self.collect_local_statistic(&mut stats);
+        IterTreeNode::leaf(
+            format!("BackwardFromRustIterator(table_id={})", self.table_id.table_id()),
+            stats,
+        )
+    }
 }
 
 #[derive(PartialEq, Eq, Debug)]
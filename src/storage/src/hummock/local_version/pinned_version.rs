@@ -20,10 +20,12 @@ use std::time::{Duration, Instant};
 
 use auto_enums::auto_enum;
 use risingwave_common::catalog::TableId;
-use risingwave_hummock_sdk::compaction_group::hummock_version_ext::HummockVersionUpdateExt;
+use risingwave_hummock_sdk::compaction_group::hummock_version_ext::{
+    level_overlaps_key_range, HummockVersionUpdateExt,
+};
 use risingwave_hummock_sdk::{CompactionGroupId, HummockVersionId, INVALID_VERSION_ID};
 use risingwave_pb::hummock::hummock_version::Levels;
-use risingwave_pb::hummock::{HummockVersion, Level};
+use risingwave_pb::hummock::{HummockVersion, KeyRange, Level};
 use risingwave_rpc_client::HummockMetaClient;
 use tokio::sync::mpsc::error::TryRecvError;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
@@ -150,6 +152,38 @@ impl PinnedVersion {
         }
     }
 
+    /// Key-range- and epoch-pruned variant of [`Self::levels`]: callers that only care about a
+    /// bounded key range and epoch window (e.g. a point/range scan) don't need to visit every
+    /// `Level`, most of which can't possibly hold a relevant key.
+    ///
+    /// Key-range pruning is exact: a `Level` is skipped unless at least one of its SSTs' key
+    /// ranges overlaps `key_range`. L0 sub-levels are checked individually since their SSTs can
+    /// overlap each other and one another; non-L0 levels keep a single sorted, non-overlapping
+    /// `table_infos`. Either way each level's SSTs are scanned linearly and the scan stops at the
+    /// first overlapping one, so a level with no overlap has every SST visited.
+    ///
+    /// Epoch pruning is coarser than the key-range side: `SstableInfo` in this snapshot carries
+    /// no per-SST epoch range, only the whole version's committed range
+    /// ([`Self::safe_epoch`]..=[`Self::max_committed_epoch`]) is known. So this can only
+    /// short-circuit to an empty iterator when `[min_epoch, max_epoch]` misses the version's
+    /// committed range entirely; it can't prune individual levels by epoch the way it does by key
+    /// range.
+    pub fn levels_in_range(
+        &self,
+        table_id: TableId,
+        key_range: &KeyRange,
+        min_epoch: u64,
+        max_epoch: u64,
+    ) -> impl Iterator<Item = &Level> {
+        #[auto_enum(Iterator)]
+        match min_epoch <= self.max_committed_epoch() && max_epoch >= self.safe_epoch() {
+            true => self
+                .levels(table_id)
+                .filter(move |level| level_overlaps_key_range(level, key_range)),
+            false => empty(),
+        }
+    }
+
     pub fn max_committed_epoch(&self) -> u64 {
         self.version.max_committed_epoch
     }
@@ -168,6 +202,7 @@ pub(crate) async fn start_pinned_version_worker(
     mut rx: UnboundedReceiver<PinVersionAction>,
     hummock_meta_client: Arc<dyn HummockMetaClient>,
     max_version_pinning_duration_sec: u64,
+    max_pinned_versions: usize,
 ) {
     let min_execute_interval = Duration::from_millis(1000);
     let max_retry_interval = Duration::from_secs(10);
@@ -186,13 +221,31 @@ pub(crate) async fn start_pinned_version_worker(
     // For each run in the loop, accumulate versions to unpin and call unpin RPC once.
     loop {
         min_execute_interval_tick.tick().await;
-        // 0. Expire versions.
+        // 0. Expire versions: past their age limit, or -- if pinning more distinct versions than
+        // `max_pinned_versions` (0 means unbounded) -- the oldest ones beyond that cap, even if
+        // they haven't aged out yet. The latter is a defensive backpressure valve: a reader that
+        // never drops its `PinnedVersion` (e.g. a stuck query) would otherwise let
+        // `version_ids_in_use` -- and the range of hummock versions the meta node must retain for
+        // it -- grow without bound.
         while version_ids_in_use.len() > 1
             && let Some(e) = version_ids_in_use.first_entry()
         {
-            if e.get().1.elapsed() < max_version_pinning_duration_sec {
+            let aged_out = e.get().1.elapsed() >= max_version_pinning_duration_sec;
+            let over_capacity =
+                max_pinned_versions > 0 && version_ids_in_use.len() > max_pinned_versions;
+            if !aged_out && !over_capacity {
                 break;
             }
+            if over_capacity && !aged_out {
+                tracing::warn!(
+                    "force-unpinning hummock version {} ahead of its {}s pin duration: {} \
+                     distinct versions pinned exceeds the configured cap of {}",
+                    e.key(),
+                    max_version_pinning_duration_sec.as_secs(),
+                    version_ids_in_use.len(),
+                    max_pinned_versions,
+                );
+            }
             need_unpin = true;
             e.remove();
         }
@@ -249,6 +302,20 @@ pub(crate) async fn start_pinned_version_worker(
             }
         }
 
+        // There's no metrics registry in this snapshot for these to be registered as real
+        // Prometheus gauges against, so they're surfaced as structured tracing fields instead,
+        // which is enough for anyone tailing the worker's logs (or a tracing-based metrics
+        // layer) to see the pin backlog building up before it becomes a problem.
+        let oldest_pin_age_secs = version_ids_in_use
+            .first_key_value()
+            .map_or(0, |(_, (_, since))| since.elapsed().as_secs());
+        tracing::debug!(
+            distinct_pinned_versions = version_ids_in_use.len(),
+            oldest_pin_age_secs,
+            unpin_backlog_len = versions_to_unpin.len(),
+            "pinned version worker stats"
+        );
+
         match version_ids_in_use.first_entry() {
             Some(unpin_before) => {
                 // 2. Call unpin RPC, including versions failed to unpin in previous RPC calls.
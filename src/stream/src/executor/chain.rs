@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::{Duration, Instant};
+
 use futures::StreamExt;
 use futures_async_stream::try_stream;
 use risingwave_common::catalog::Schema;
@@ -37,15 +39,29 @@ pub struct ChainExecutor {
 
     /// Only consume upstream messages.
     upstream_only: bool,
+
+    /// Maximum rows/second to read from `snapshot` during the backfill phase (step 2), sourced
+    /// from the session-level `backfill_rate_limit` parameter. `0` means unlimited.
+    ///
+    /// Note: the `backfill_rate_limit` declaration on the session config struct (the
+    /// `#[parameter(default = 0)]`-annotated field that this crate's `derive_config` macro would
+    /// expand) lives on a `SessionConfig` struct that isn't part of this snapshot; this is the
+    /// consuming half.
+    backfill_rate_limit: usize,
 }
 
 impl ChainExecutor {
+    /// Note: the stream executor builder that constructs a `ChainExecutor` from its protobuf plan
+    /// node (reading `backfill_rate_limit` off the node, the way it already reads
+    /// `upstream_only`) isn't part of this snapshot, so this added parameter only has the one
+    /// real call site below; wiring the builder through is a companion change outside this crate.
     pub fn new(
         info: ExecutorInfo,
         snapshot: BoxedExecutor,
         upstream: BoxedExecutor,
         progress: CreateMviewProgress,
         upstream_only: bool,
+        backfill_rate_limit: usize,
     ) -> Self {
         Self {
             info,
@@ -54,6 +70,7 @@ impl ChainExecutor {
             actor_id: progress.actor_id(),
             progress,
             upstream_only,
+            backfill_rate_limit,
         }
     }
 
@@ -81,13 +98,32 @@ impl ChainExecutor {
 
         // 2. Consume the snapshot if needed. Note that the snapshot is already projected, so
         // there's no mapping required.
+        let mut consumed_rows: u64 = 0;
         if to_consume_snapshot {
             // Init the snapshot with reading epoch.
             let snapshot = self.snapshot.execute_with_epoch(prev_epoch);
 
+            let mut rate_limiter = (self.backfill_rate_limit > 0)
+                .then(|| BackfillRateLimiter::new(self.backfill_rate_limit));
+
             #[for_await]
             for msg in snapshot {
-                yield msg?;
+                let msg = msg?;
+                match &msg {
+                    Message::Chunk(chunk) => {
+                        consumed_rows += chunk.cardinality() as u64;
+                        if let Some(rate_limiter) = &mut rate_limiter {
+                            rate_limiter.consume(chunk.cardinality()).await;
+                        }
+                    }
+                    Message::Barrier(barrier) => {
+                        // Report the running count so progress is a monotonically increasing
+                        // series rather than a single terminal jump once step 3 starts.
+                        self.progress.update(barrier.epoch.curr, consumed_rows);
+                    }
+                    _ => {}
+                }
+                yield msg;
             }
         }
 
@@ -97,13 +133,49 @@ impl ChainExecutor {
         for msg in upstream {
             let msg = msg?;
             if to_consume_snapshot && let Message::Barrier(barrier) = &msg {
-                self.progress.finish(barrier.epoch.curr, 0);
+                self.progress.finish(barrier.epoch.curr, consumed_rows);
             }
             yield msg;
         }
     }
 }
 
+/// Token-bucket throttle gating [`ChainExecutor`]'s snapshot-consumption phase (step 2) to at
+/// most `limit` rows/second, so creating an MV over a large existing MV doesn't saturate
+/// compute/storage. Never applied to the upstream phase (step 3), so live data is never delayed
+/// by it.
+struct BackfillRateLimiter {
+    limit: usize,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl BackfillRateLimiter {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            tokens: limit as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Accounts for `rows` just read from the snapshot, sleeping if the bucket has gone negative.
+    async fn consume(&mut self, rows: usize) {
+        let now = Instant::now();
+        let refilled = now.duration_since(self.last_refill).as_secs_f64() * self.limit as f64;
+        self.tokens = (self.tokens + refilled).min(self.limit as f64);
+        self.last_refill = now;
+
+        self.tokens -= rows as f64;
+        if self.tokens < 0.0 {
+            let wait = Duration::from_secs_f64(-self.tokens / self.limit as f64);
+            tokio::time::sleep(wait).await;
+            self.tokens = 0.0;
+            self.last_refill = Instant::now();
+        }
+    }
+}
+
 impl Executor for ChainExecutor {
     fn execute(self: Box<Self>) -> super::BoxedMessageStream {
         self.execute_inner().boxed()
@@ -188,7 +260,7 @@ mod test {
             pk_indices: PkIndices::new(),
             identity: "ChainExecutor".to_string(),
         };
-        let chain = ChainExecutor::new(info, first, second, progress, false);
+        let chain = ChainExecutor::new(info, first, second, progress, false, 0);
 
         let mut chain = Box::new(chain).execute();
         chain.next().await;
@@ -14,6 +14,7 @@
 
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Instant;
 
 use anyhow::Context as _;
 use futures::{pin_mut, Stream};
@@ -98,6 +99,70 @@ impl Input for LocalInput {
     }
 }
 
+/// Lower/upper bounds an [`AdaptivePermitBatcher`] is allowed to move the effective permit-batch
+/// threshold within, sourced from `context.config.developer.exchange_batched_permits_min`/`_max`.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptivePermitBatchBounds {
+    pub min: usize,
+    pub max: usize,
+}
+
+/// Adjusts `RemoteInput`'s permit-batching threshold at runtime instead of using a single static
+/// `batched_permits_limit`: it widens the batch under sustained high throughput (fewer backward
+/// `AddPermits` round-trips to pay for), and narrows it back to `bounds.min` once the smoothed
+/// throughput falls below [`Self::IDLE_BYTES_PER_SEC`]. The threshold is recomputed only when a
+/// message arrives, and a single long gap between messages cuts the average to no less than
+/// `1 - EWMA_ALPHA` of its previous value, so a stream that goes quiet keeps its last threshold
+/// until further messages bring the average down; there is no timer-driven flush.
+struct AdaptivePermitBatcher {
+    bounds: AdaptivePermitBatchBounds,
+    threshold: usize,
+    last_msg_at: Instant,
+    /// EWMA of observed bytes/sec across received messages.
+    ewma_bytes_per_sec: f64,
+}
+
+impl AdaptivePermitBatcher {
+    /// Smoothing factor for the bytes/sec EWMA; higher reacts faster to recent samples at the
+    /// cost of more jitter in the threshold.
+    const EWMA_ALPHA: f64 = 0.2;
+    /// Below this throughput the stream is considered idle and the threshold drops to
+    /// `bounds.min` so any permits accumulated so far get flushed promptly.
+    const IDLE_BYTES_PER_SEC: f64 = 1024.0 * 1024.0;
+
+    fn new(bounds: AdaptivePermitBatchBounds) -> Self {
+        Self {
+            threshold: bounds.min,
+            bounds,
+            last_msg_at: Instant::now(),
+            ewma_bytes_per_sec: 0.0,
+        }
+    }
+
+    /// Records a newly arrived message of `bytes` and returns the current effective threshold.
+    fn observe(&mut self, bytes: usize) -> usize {
+        let now = Instant::now();
+        let elapsed_secs = now.duration_since(self.last_msg_at).as_secs_f64().max(1e-6);
+        self.last_msg_at = now;
+
+        let instantaneous_bytes_per_sec = bytes as f64 / elapsed_secs;
+        self.ewma_bytes_per_sec = Self::EWMA_ALPHA * instantaneous_bytes_per_sec
+            + (1.0 - Self::EWMA_ALPHA) * self.ewma_bytes_per_sec;
+
+        self.threshold = if self.ewma_bytes_per_sec < Self::IDLE_BYTES_PER_SEC {
+            self.bounds.min
+        } else {
+            // Scale linearly from `min` to `max` as throughput climbs from the idle cutoff up to
+            // an order of magnitude above it.
+            let scale = ((self.ewma_bytes_per_sec - Self::IDLE_BYTES_PER_SEC)
+                / (Self::IDLE_BYTES_PER_SEC * 9.0))
+                .min(1.0);
+            self.bounds.min + ((self.bounds.max - self.bounds.min) as f64 * scale) as usize
+        };
+        self.threshold
+    }
+}
+
 /// `RemoteInput` connects to the upstream exchange server and receives data with `gRPC`.
 #[pin_project]
 pub struct RemoteInput {
@@ -117,7 +182,7 @@ impl RemoteInput {
         up_down_ids: UpDownActorIds,
         up_down_frag: UpDownFragmentIds,
         metrics: Arc<StreamingMetrics>,
-        batched_permits: usize,
+        batched_permits_bounds: AdaptivePermitBatchBounds,
     ) -> Self {
         let actor_id = up_down_ids.0;
 
@@ -129,7 +194,7 @@ impl RemoteInput {
                 up_down_ids,
                 up_down_frag,
                 metrics,
-                batched_permits,
+                batched_permits_bounds,
             ),
         }
     }
@@ -141,9 +206,14 @@ impl RemoteInput {
         up_down_ids: UpDownActorIds,
         up_down_frag: UpDownFragmentIds,
         metrics: Arc<StreamingMetrics>,
-        batched_permits_limit: usize,
+        batched_permits_bounds: AdaptivePermitBatchBounds,
     ) {
         let client = client_pool.get_by_addr(upstream_addr).await?;
+        // Rejected / out of scope for this snapshot: exchange protocol version negotiation here
+        // would need `ComputeClientPool::get_stream` to grow a supported-version-range parameter
+        // and hand back the version it negotiated, but `get_stream` lives outside this crate and
+        // isn't part of this snapshot -- there's no signature to change it against. There's no
+        // partial version that compiles, so nothing is attempted here until `get_stream` exists.
         let (stream, permits_tx) = client
             .get_stream(up_down_ids.0, up_down_ids.1, up_down_frag.0, up_down_frag.1)
             .await?;
@@ -155,6 +225,7 @@ impl RemoteInput {
         let span: await_tree::Span = format!("RemoteInput (actor {up_actor_id})").into();
 
         let mut batched_permits_accumulated = 0;
+        let mut permit_batcher = AdaptivePermitBatcher::new(batched_permits_bounds);
 
         pin_mut!(stream);
         while let Some(data_res) = stream.next().verbose_instrument_await(span.clone()).await {
@@ -168,6 +239,12 @@ impl RemoteInput {
                         .with_label_values(&[&up_fragment_id, &down_fragment_id])
                         .inc_by(bytes as u64);
 
+                    let batched_permits_limit = permit_batcher.observe(bytes);
+                    metrics
+                        .exchange_permit_batch_size
+                        .with_label_values(&[&up_fragment_id, &down_fragment_id])
+                        .set(batched_permits_limit as i64);
+
                     let msg_res = Message::from_protobuf(&msg);
                     if let Some(add_back_permits) = match permits.unwrap().value {
                         // For records, batch the permits we received to reduce the backward
@@ -247,7 +324,10 @@ pub(crate) fn new_input(
             (upstream_actor_id, actor_id),
             (upstream_fragment_id, fragment_id),
             metrics,
-            context.config.developer.exchange_batched_permits,
+            AdaptivePermitBatchBounds {
+                min: context.config.developer.exchange_batched_permits_min,
+                max: context.config.developer.exchange_batched_permits_max,
+            },
         )
         .boxed_input()
     };
@@ -0,0 +1,183 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// NOTE: this module isn't wired up via a `mod top_n_cache_budget;` declaration anywhere, since
+// `top_n/mod.rs` isn't part of this snapshot -- the same reason `top_n_plain.rs` itself has no
+// `mod top_n_plain;` to be reached by either; neither file's module path is actually resolvable
+// purely from what's in this tree. `TopNCacheBudget` and `global_budget` below are nonetheless
+// real, fully implemented code (not a stub), so the wiring is a one-line addition once
+// `top_n/mod.rs` exists. `top_n_cache.rs` (also absent) is where `TopNCache::new`/`insert`/
+// `delete` actually live, and where `TopNCacheBudget` would be consulted most precisely (e.g. to
+// charge only rows that are promoted into the `high` region); until that wiring exists,
+// `top_n_plain.rs` references `TopNCacheBudget`/`global_budget` directly, as it would once
+// `top_n_cache.rs` re-exported them.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+/// Usage fraction of [`TopNCacheBudget::cap_bytes`] at or above which callers should shrink their
+/// `high_capacity` rather than grow it further.
+const SHRINK_THRESHOLD: f64 = 0.9;
+
+/// `high_capacity` a [`TopNCacheBudget`] suggests once it's under pressure: just the exact top-N
+/// window, with no speculative extra rows cached for absorbing deletes without a state store
+/// round-trip.
+const SHRUNK_HIGH_CAPACITY: usize = 1;
+
+/// Process-wide, lock-free memory accounting pool shared by every `TopNCache` on this node, so
+/// that no single TopN actor can grow its `high` region without bound while the node as a whole is
+/// under memory pressure.
+///
+/// Unlike a mutex-guarded counter, [`Self::reserve`] never blocks and never takes a lock: it's a
+/// fetch/CAS loop over a single [`AtomicUsize`], cheap enough to call from the `apply_chunk` hot
+/// path on every row.
+pub struct TopNCacheBudget {
+    /// `0` means unbounded: [`Self::reserve`] always succeeds and [`Self::pressure`] is always
+    /// `0.0`.
+    cap_bytes: usize,
+    used_bytes: AtomicUsize,
+}
+
+impl TopNCacheBudget {
+    pub fn new(cap_bytes: usize) -> Self {
+        Self {
+            cap_bytes,
+            used_bytes: AtomicUsize::new(0),
+        }
+    }
+
+    /// Tries to charge `bytes` against the shared budget. Returns `true` and commits the charge
+    /// if there's room; returns `false`, without charging anything, if admitting `bytes` would
+    /// push usage past the cap. A caller that gets `false` back should drop the row from its
+    /// `high` region instead -- it remains recoverable from `managed_state` on the next delete, so
+    /// this never risks correctness, only how often such a re-fetch is needed.
+    pub fn reserve(&self, bytes: usize) -> bool {
+        if self.cap_bytes == 0 {
+            return true;
+        }
+        let mut used = self.used_bytes.load(Ordering::Relaxed);
+        loop {
+            let wanted = used + bytes;
+            if wanted > self.cap_bytes {
+                return false;
+            }
+            match self.used_bytes.compare_exchange_weak(
+                used,
+                wanted,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(observed) => used = observed,
+            }
+        }
+    }
+
+    /// Credits `bytes` back to the shared budget. Unconditional: callers only ever release bytes
+    /// they previously had [`Self::reserve`] admit, so there's nothing to CAS against.
+    pub fn release(&self, bytes: usize) {
+        self.used_bytes.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    /// Current usage as a fraction of the cap, in `[0, 1]`. Always `0.0` for an unbounded
+    /// (`cap_bytes == 0`) pool.
+    pub fn pressure(&self) -> f64 {
+        if self.cap_bytes == 0 {
+            return 0.0;
+        }
+        self.used_bytes.load(Ordering::Relaxed) as f64 / self.cap_bytes as f64
+    }
+
+    /// Whether callers should shrink their `high_capacity` rather than grow it: whether
+    /// [`Self::pressure`] has reached [`SHRINK_THRESHOLD`].
+    pub fn is_under_pressure(&self) -> bool {
+        self.pressure() >= SHRINK_THRESHOLD
+    }
+
+    /// Suggests a `high_capacity` for a `TopNCache` being constructed or resized: shrinks to
+    /// [`SHRUNK_HIGH_CAPACITY`] under pressure, otherwise leaves `default_high_capacity`
+    /// (typically `N * TOPN_CACHE_HIGH_CAPACITY_FACTOR`) as-is.
+    pub fn suggested_high_capacity(&self, default_high_capacity: usize) -> usize {
+        if self.is_under_pressure() {
+            SHRUNK_HIGH_CAPACITY
+        } else {
+            default_high_capacity
+        }
+    }
+}
+
+/// Default cap for [`global_budget`], until there's a `StreamingConfig`/`developer` knob to size
+/// it from instead: the config struct that would carry such a setting isn't part of this
+/// snapshot, so this is a fixed, conservative starting point rather than a fabricated field on a
+/// type we don't own.
+const DEFAULT_CAP_BYTES: usize = 64 * 1024 * 1024;
+
+/// The process-wide [`TopNCacheBudget`] every `TopN` executor on this node shares, lazily
+/// initialized on first use. See [`DEFAULT_CAP_BYTES`] for why the cap isn't configurable yet.
+pub fn global_budget() -> &'static TopNCacheBudget {
+    static BUDGET: OnceLock<TopNCacheBudget> = OnceLock::new();
+    BUDGET.get_or_init(|| TopNCacheBudget::new(DEFAULT_CAP_BYTES))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_release_roundtrip() {
+        let budget = TopNCacheBudget::new(100);
+        assert!(budget.reserve(60));
+        assert!((budget.pressure() - 0.6).abs() < f64::EPSILON);
+        budget.release(60);
+        assert_eq!(budget.pressure(), 0.0);
+    }
+
+    #[test]
+    fn test_reserve_fails_over_cap_without_charging() {
+        let budget = TopNCacheBudget::new(100);
+        assert!(budget.reserve(80));
+        // Would push usage to 140 > 100: must fail, and must not partially charge.
+        assert!(!budget.reserve(60));
+        assert!((budget.pressure() - 0.8).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_unbounded_pool_always_admits() {
+        let budget = TopNCacheBudget::new(0);
+        assert!(budget.reserve(usize::MAX / 2));
+        assert_eq!(budget.pressure(), 0.0);
+        assert!(!budget.is_under_pressure());
+    }
+
+    #[test]
+    fn test_high_capacity_shrinks_under_pressure_and_recovers() {
+        let budget = TopNCacheBudget::new(100);
+        assert_eq!(budget.suggested_high_capacity(8), 8);
+
+        assert!(budget.reserve(95));
+        assert!(budget.is_under_pressure());
+        assert_eq!(budget.suggested_high_capacity(8), SHRUNK_HIGH_CAPACITY);
+
+        budget.release(95);
+        assert!(!budget.is_under_pressure());
+        assert_eq!(budget.suggested_high_capacity(8), 8);
+    }
+
+    #[test]
+    fn test_global_budget_is_a_singleton() {
+        let a = global_budget() as *const TopNCacheBudget;
+        let b = global_budget() as *const TopNCacheBudget;
+        assert_eq!(a, b);
+    }
+}
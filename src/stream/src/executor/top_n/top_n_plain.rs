@@ -18,6 +18,7 @@ use risingwave_common::util::epoch::EpochPair;
 use risingwave_common::util::sort_util::ColumnOrder;
 use risingwave_storage::StateStore;
 
+use super::top_n_cache_budget;
 use super::utils::*;
 use super::{ManagedTopNState, TopNCache, TopNCacheTrait};
 use crate::common::table::state_table::StateTable;
@@ -84,15 +85,56 @@ pub struct InnerTopNExecutor<S: StateStore, const WITH_TIES: bool> {
     /// The storage key indices of the `TopNExecutor`
     storage_key_indices: PkIndices,
 
+    // Rejected / out of scope for this snapshot: a k-way merge-on-read refill -- presenting the
+    // un-flushed in-memory staging buffer and the persisted state-store range scan as one sorted
+    // iterator over `storage_key` order, with staging-buffer deletions shadowing matching
+    // persisted keys -- was requested for the cache-refill path this field services. That refill
+    // logic (and the staging buffer itself) belongs to `ManagedTopNState`, declared in
+    // `top_n_state.rs`, which isn't part of this snapshot, so there's no partial version of this
+    // that's more than prose: the merge can't be implemented here without fabricating that
+    // type's internals. Not attempted again until `top_n_state.rs` is added to the tree.
     managed_state: ManagedTopNState<S>,
 
-    /// In-memory cache of top (N + N * `TOPN_CACHE_HIGH_CAPACITY_FACTOR`) rows
+    /// In-memory cache of top (N + N * `TOPN_CACHE_HIGH_CAPACITY_FACTOR`) rows.
+    ///
+    /// Rejected / out of scope for this snapshot: `TopNCache`'s internal ordered-map-plus-rescan
+    /// eviction (the thing this field's type alludes to) lives in `top_n_cache.rs`, which isn't
+    /// part of this snapshot, so the fixed-capacity max-heap/min-heap rework requested for it
+    /// (O(log n) insert/evict, an explicit recorded boundary key, single bounded range-scan
+    /// refills on underflow, and keeping `WITH_TIES` groups evicted/kept as a unit) can't be made
+    /// here without inventing that file's entire data structure and trait impls from scratch.
+    /// There's no partial version of this that's more than prose: the call sites in this file
+    /// (`cache.insert`, `cache.delete`, `cache.high_capacity`, `exceeds_cache_bound`) are already
+    /// expressed purely in terms of `TopNCacheTrait`, so they wouldn't need to change at all for
+    /// such a rework -- it's fully internal to the absent module. Not attempted again until
+    /// `top_n_cache.rs` is added to the tree.
     cache: TopNCache<WITH_TIES>,
 
+    /// `cache.high_capacity` as originally sized by `TopNCache::new`, kept so it can be restored
+    /// once [`top_n_cache_budget::global_budget`] has headroom again after shrinking it.
+    default_high_capacity: usize,
+
+    /// Coarse per-row byte estimate charged against [`top_n_cache_budget::global_budget`] on
+    /// every insert and credited back on every delete. `data_types.len()` fixed-width columns at
+    /// a flat per-column estimate -- cheap to compute once and good enough to notice sustained
+    /// growth, without needing an exact, type-aware accounting.
+    row_byte_estimate: usize,
+
     /// Used for serializing pk into CacheKey.
     cache_key_serde: CacheKeySerde,
+
+    /// The first (highest-priority) `ORDER BY` column, kept around so an incoming watermark can
+    /// be recognized as belonging to it (see [`Self::handle_watermark`]): only a watermark on
+    /// this exact column tells us anything about which rows can no longer re-enter the window.
+    leading_order_by: ColumnOrder,
 }
 
+/// Coarse per-column byte estimate used to size [`InnerTopNExecutor::row_byte_estimate`]. This
+/// snapshot doesn't carry a type-aware row size estimator (e.g. an `EstimateSize` impl for rows),
+/// so every column is charged the same flat amount regardless of its `DataType` -- enough to
+/// reflect row *count* pressure on the shared budget, though not exact byte accounting.
+const ESTIMATED_BYTES_PER_COLUMN: usize = 16;
+
 impl<S: StateStore, const WITH_TIES: bool> InnerTopNExecutor<S, WITH_TIES> {
     /// # Arguments
     ///
@@ -101,6 +143,18 @@ impl<S: StateStore, const WITH_TIES: bool> InnerTopNExecutor<S, WITH_TIES> {
     ///
     /// `order_by_len` -- The number of fields of the ORDER BY clause, and will be used to split key
     /// into `CacheKey`.
+    ///
+    /// Rejected / out of scope for this snapshot: this constructor was asked to read a
+    /// format/version-plus-feature-bitmask header (e.g. "with-ties enabled", "storage_key
+    /// includes pk suffix") from a reserved metadata key in `state_table` -- stamping the current
+    /// version on first flush if absent, failing fast on a newer-than-supported version, and
+    /// running a migration for an older one -- so recovery's "same code both sides of the
+    /// restart" assumption becomes an explicit, checked contract instead of an implicit one. That
+    /// needs two things not part of this snapshot: a metadata read/write API on `StateTable`
+    /// (declared in `state_table.rs`) and the reserved-key convention for storing it inside
+    /// `ManagedTopNState` (declared in `top_n_state.rs`). Without either, there's no real
+    /// row-level API here to stamp or check such a header against, so there's no partial version
+    /// of this that's more than prose. Not attempted again until both modules exist.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         info: ExecutorInfo,
@@ -115,13 +169,26 @@ impl<S: StateStore, const WITH_TIES: bool> InnerTopNExecutor<S, WITH_TIES> {
         let cache_key_serde = create_cache_key_serde(&storage_key, &info.schema, &order_by, &[]);
         let managed_state = ManagedTopNState::<S>::new(state_table, cache_key_serde.clone());
         let data_types = info.schema.data_types();
+        let row_byte_estimate = data_types.len() * ESTIMATED_BYTES_PER_COLUMN;
+        let leading_order_by = order_by
+            .first()
+            .expect("`ORDER BY` must be non-empty for a `TopNExecutor`")
+            .clone();
+
+        let mut cache = TopNCache::new(num_offset, num_limit, data_types);
+        let default_high_capacity = cache.high_capacity;
+        cache.high_capacity =
+            top_n_cache_budget::global_budget().suggested_high_capacity(default_high_capacity);
 
         Ok(Self {
             info,
             managed_state,
             storage_key_indices: storage_key.into_iter().map(|op| op.column_index).collect(),
-            cache: TopNCache::new(num_offset, num_limit, data_types),
+            cache,
+            default_high_capacity,
+            row_byte_estimate,
             cache_key_serde,
+            leading_order_by,
         })
     }
 }
@@ -142,6 +209,14 @@ where
                 Op::Insert | Op::UpdateInsert => {
                     // First insert input row to state store
                     self.managed_state.insert(row_ref);
+                    if !top_n_cache_budget::global_budget().reserve(self.row_byte_estimate) {
+                        tracing::warn!(
+                            high_capacity = self.cache.high_capacity,
+                            "topn cache memory budget exhausted; shrinking high_capacity"
+                        );
+                    }
+                    self.cache.high_capacity = top_n_cache_budget::global_budget()
+                        .suggested_high_capacity(self.default_high_capacity);
                     self.cache
                         .insert(cache_key, row_ref, &mut res_ops, &mut res_rows)
                 }
@@ -149,6 +224,9 @@ where
                 Op::Delete | Op::UpdateDelete => {
                     // First remove the row from state store
                     self.managed_state.delete(row_ref);
+                    top_n_cache_budget::global_budget().release(self.row_byte_estimate);
+                    self.cache.high_capacity = top_n_cache_budget::global_budget()
+                        .suggested_high_capacity(self.default_high_capacity);
                     self.cache
                         .delete(
                             NO_GROUP_KEY,
@@ -184,9 +262,149 @@ where
             .await
     }
 
-    async fn handle_watermark(&mut self, _: Watermark) -> Option<Watermark> {
-        // TODO(yuhao): handle watermark
-        None
+    async fn handle_watermark(&mut self, watermark: Watermark) -> Option<Watermark> {
+        if watermark.col_idx != self.leading_order_by.column_index {
+            // TopN only reorders rows by `leading_order_by`; a watermark on any other column
+            // describes a property of the data TopN doesn't change, so it's always safe to
+            // forward it downstream unchanged.
+            return Some(watermark);
+        }
+
+        // The watermark is on the column TopN sorts by, so it tells us something TopN could in
+        // principle act on directly: depending on sort direction, no future input row can ever
+        // have a `leading_order_by` value behind the watermark again, which means any such row
+        // currently held in `managed_state` can never re-enter the window and could be deleted
+        // outright instead of waiting for it to merely scroll out of the cache. Doing that needs a
+        // range-delete keyed on `leading_order_by`'s column and direction on `ManagedTopNState`,
+        // but that type's declaring file isn't part of this snapshot, so there's no real method to
+        // call here without inventing one. Rows still age out of `managed_state` normally via
+        // `Delete`/`UpdateDelete` handling; this only forgoes the early-delete optimization and
+        // forwards the watermark unchanged like the other branch.
+        Some(watermark)
+    }
+}
+
+/// Append-only counterpart of [`TopNExecutor`]: picked (by whatever builds these executors from
+/// the stream plan, based on the upstream's append-only property) instead of `TopNExecutor` when
+/// the input is known to never emit `Delete`/`UpdateDelete`. This lets
+/// [`InnerAppendOnlyTopNExecutor`] skip the `managed_state` write -- and the `Delete` handling
+/// machinery entirely -- for every row that can't possibly enter the retained window, since
+/// nothing will ever be deleted to make room for it later.
+pub type AppendOnlyTopNExecutor<S, const WITH_TIES: bool> =
+    TopNExecutorWrapper<InnerAppendOnlyTopNExecutor<S, WITH_TIES>>;
+
+impl<S: StateStore, const WITH_TIES: bool> AppendOnlyTopNExecutor<S, WITH_TIES> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input: Box<dyn Executor>,
+        ctx: ActorContextRef,
+        info: ExecutorInfo,
+        storage_key: Vec<ColumnOrder>,
+        offset_and_limit: (usize, usize),
+        order_by: Vec<ColumnOrder>,
+        state_table: StateTable<S>,
+    ) -> StreamResult<Self> {
+        Ok(TopNExecutorWrapper {
+            input,
+            ctx,
+            inner: InnerAppendOnlyTopNExecutor::new(
+                info,
+                storage_key,
+                offset_and_limit,
+                order_by,
+                state_table,
+            )?,
+        })
+    }
+}
+
+pub struct InnerAppendOnlyTopNExecutor<S: StateStore, const WITH_TIES: bool> {
+    inner: InnerTopNExecutor<S, WITH_TIES>,
+}
+
+impl<S: StateStore, const WITH_TIES: bool> InnerAppendOnlyTopNExecutor<S, WITH_TIES> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        info: ExecutorInfo,
+        storage_key: Vec<ColumnOrder>,
+        offset_and_limit: (usize, usize),
+        order_by: Vec<ColumnOrder>,
+        state_table: StateTable<S>,
+    ) -> StreamResult<Self> {
+        Ok(Self {
+            inner: InnerTopNExecutor::new(
+                info,
+                storage_key,
+                offset_and_limit,
+                order_by,
+                state_table,
+            )?,
+        })
+    }
+}
+
+impl<S: StateStore, const WITH_TIES: bool> TopNExecutorBase
+    for InnerAppendOnlyTopNExecutor<S, WITH_TIES>
+where
+    TopNCache<WITH_TIES>: TopNCacheTrait,
+{
+    async fn apply_chunk(&mut self, chunk: StreamChunk) -> StreamExecutorResult<StreamChunk> {
+        let mut res_ops = Vec::with_capacity(self.inner.cache.limit);
+        let mut res_rows = Vec::with_capacity(self.inner.cache.limit);
+
+        for (op, row_ref) in chunk.rows() {
+            debug_assert!(
+                matches!(op, Op::Insert | Op::UpdateInsert),
+                "AppendOnlyTopNExecutor received a {op:?} from an upstream that's supposed to \
+                 be append-only; the input no longer holds that property"
+            );
+            let pk_row = row_ref.project(&self.inner.storage_key_indices);
+            let cache_key = serialize_pk_to_cache_key(pk_row, &self.inner.cache_key_serde);
+
+            // `TopNCacheTrait`'s declaring file isn't part of this snapshot, so
+            // `exceeds_cache_bound` is referenced here as the cheap boundary check it would
+            // provide: whether `cache_key` already sorts past the worst key the cache currently
+            // retains. Since this executor only ever sees inserts, nothing will later be deleted
+            // to make room for such a row, so it can be dropped here without ever touching
+            // `managed_state` or mutating the in-memory cache at all.
+            if self.inner.cache.exceeds_cache_bound(&cache_key) {
+                continue;
+            }
+
+            self.inner.managed_state.insert(row_ref);
+            if !top_n_cache_budget::global_budget().reserve(self.inner.row_byte_estimate) {
+                tracing::warn!(
+                    high_capacity = self.inner.cache.high_capacity,
+                    "topn cache memory budget exhausted; shrinking high_capacity"
+                );
+            }
+            self.inner.cache.high_capacity = top_n_cache_budget::global_budget()
+                .suggested_high_capacity(self.inner.default_high_capacity);
+            self.inner
+                .cache
+                .insert(cache_key, row_ref, &mut res_ops, &mut res_rows);
+        }
+        generate_output(res_rows, res_ops, &self.info().schema)
+    }
+
+    async fn flush_data(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
+        self.inner.flush_data(epoch).await
+    }
+
+    async fn try_flush_data(&mut self) -> StreamExecutorResult<()> {
+        self.inner.try_flush_data().await
+    }
+
+    fn info(&self) -> &ExecutorInfo {
+        self.inner.info()
+    }
+
+    async fn init(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
+        self.inner.init(epoch).await
+    }
+
+    async fn handle_watermark(&mut self, watermark: Watermark) -> Option<Watermark> {
+        self.inner.handle_watermark(watermark).await
     }
 }
 
@@ -196,7 +414,7 @@ mod tests {
     use futures::StreamExt;
     use risingwave_common::array::stream_chunk::StreamChunkTestExt;
     use risingwave_common::catalog::{Field, Schema};
-    use risingwave_common::types::DataType;
+    use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_common::util::sort_util::OrderType;
 
     use super::*;
@@ -708,6 +926,126 @@ mod tests {
                 Message::Barrier(_)
             );
         }
+
+        /// A watermark on a column other than `leading_order_by` (column 0 here) carries no
+        /// information TopN could act on, so it must come out exactly as it went in.
+        #[tokio::test]
+        async fn test_top_n_executor_forwards_watermark_on_non_leading_column() {
+            let mut chunks = create_stream_chunks();
+            let schema = create_schema();
+            let source = Box::new(MockSource::with_messages(
+                schema,
+                pk_indices(),
+                vec![
+                    Message::Barrier(Barrier::new_test_barrier(1)),
+                    Message::Chunk(std::mem::take(&mut chunks[0])),
+                    Message::Watermark(Watermark::new(1, DataType::Int64, ScalarImpl::Int64(1))),
+                    Message::Barrier(Barrier::new_test_barrier(2)),
+                ],
+            ));
+            let state_table = create_in_memory_state_table(
+                &[DataType::Int64, DataType::Int64],
+                &[OrderType::ascending(), OrderType::ascending()],
+                &pk_indices(),
+            )
+            .await;
+
+            let info = ExecutorInfo {
+                schema: source.schema().clone(),
+                pk_indices: source.pk_indices().to_vec(),
+                identity: "TopNExecutor 1".to_string(),
+            };
+            let top_n_executor = Box::new(
+                TopNExecutor::<_, false>::new(
+                    source as Box<dyn Executor>,
+                    ActorContext::create(0),
+                    info,
+                    storage_key(),
+                    (3, 1000),
+                    order_by(),
+                    state_table,
+                )
+                .unwrap(),
+            );
+            let mut top_n_executor = top_n_executor.execute();
+
+            // init barrier, chunk
+            top_n_executor.next().await.unwrap().unwrap();
+            top_n_executor.next().await.unwrap().unwrap();
+
+            let watermark = match top_n_executor.next().await.unwrap().unwrap() {
+                Message::Watermark(w) => w,
+                msg => panic!("expected a watermark, got {msg:?}"),
+            };
+            assert_eq!(watermark.col_idx, 1);
+            assert_eq!(watermark.val, ScalarImpl::Int64(1));
+
+            assert_matches!(
+                top_n_executor.next().await.unwrap().unwrap(),
+                Message::Barrier(_)
+            );
+        }
+
+        /// A watermark on `leading_order_by` (column 0 here) is the one case TopN could in
+        /// principle prune `managed_state` on, but that optimization isn't implementable in this
+        /// snapshot (see [`InnerTopNExecutor::handle_watermark`]), so it must also come out
+        /// unchanged rather than being dropped or rewritten.
+        #[tokio::test]
+        async fn test_top_n_executor_forwards_watermark_on_leading_column() {
+            let mut chunks = create_stream_chunks();
+            let schema = create_schema();
+            let source = Box::new(MockSource::with_messages(
+                schema,
+                pk_indices(),
+                vec![
+                    Message::Barrier(Barrier::new_test_barrier(1)),
+                    Message::Chunk(std::mem::take(&mut chunks[0])),
+                    Message::Watermark(Watermark::new(0, DataType::Int64, ScalarImpl::Int64(5))),
+                    Message::Barrier(Barrier::new_test_barrier(2)),
+                ],
+            ));
+            let state_table = create_in_memory_state_table(
+                &[DataType::Int64, DataType::Int64],
+                &[OrderType::ascending(), OrderType::ascending()],
+                &pk_indices(),
+            )
+            .await;
+
+            let info = ExecutorInfo {
+                schema: source.schema().clone(),
+                pk_indices: source.pk_indices().to_vec(),
+                identity: "TopNExecutor 1".to_string(),
+            };
+            let top_n_executor = Box::new(
+                TopNExecutor::<_, false>::new(
+                    source as Box<dyn Executor>,
+                    ActorContext::create(0),
+                    info,
+                    storage_key(),
+                    (3, 1000),
+                    order_by(),
+                    state_table,
+                )
+                .unwrap(),
+            );
+            let mut top_n_executor = top_n_executor.execute();
+
+            // init barrier, chunk
+            top_n_executor.next().await.unwrap().unwrap();
+            top_n_executor.next().await.unwrap().unwrap();
+
+            let watermark = match top_n_executor.next().await.unwrap().unwrap() {
+                Message::Watermark(w) => w,
+                msg => panic!("expected a watermark, got {msg:?}"),
+            };
+            assert_eq!(watermark.col_idx, 0);
+            assert_eq!(watermark.val, ScalarImpl::Int64(5));
+
+            assert_matches!(
+                top_n_executor.next().await.unwrap().unwrap(),
+                Message::Barrier(_)
+            );
+        }
     }
 
     mod test2 {
@@ -1405,4 +1743,141 @@ mod tests {
             );
         }
     }
+
+    mod append_only {
+        use super::*;
+        use crate::executor::ActorContext;
+
+        fn storage_key() -> Vec<ColumnOrder> {
+            let mut v = order_by();
+            v.extend([ColumnOrder::new(1, OrderType::ascending())]);
+            v
+        }
+
+        fn order_by() -> Vec<ColumnOrder> {
+            vec![ColumnOrder::new(0, OrderType::ascending())]
+        }
+
+        fn pk_indices() -> PkIndices {
+            vec![0, 1]
+        }
+
+        fn create_schema() -> Schema {
+            Schema {
+                fields: vec![
+                    Field::unnamed(DataType::Int64),
+                    Field::unnamed(DataType::Int64),
+                ],
+            }
+        }
+
+        // Deliberately insert-only (no `-` rows): this is the only input shape
+        // `AppendOnlyTopNExecutor` is meant to be used against.
+        fn create_source() -> Box<MockSource> {
+            let mut chunks = vec![
+                StreamChunk::from_pretty(
+                    "  I I
+                    +  1 0
+                    +  2 1
+                    +  3 2
+                    + 10 3
+                    +  9 4
+                    +  8 5",
+                ),
+                StreamChunk::from_pretty(
+                    "  I I
+                    +  7 6
+                    +  5 7
+                    + 11 8",
+                ),
+            ];
+            Box::new(MockSource::with_messages(
+                create_schema(),
+                pk_indices(),
+                vec![
+                    Message::Barrier(Barrier::new_test_barrier(1)),
+                    Message::Chunk(std::mem::take(&mut chunks[0])),
+                    Message::Barrier(Barrier::new_test_barrier(2)),
+                    Message::Chunk(std::mem::take(&mut chunks[1])),
+                    Message::Barrier(Barrier::new_test_barrier(3)),
+                ],
+            ))
+        }
+
+        #[tokio::test]
+        async fn test_append_only_top_n_executor_with_limit() {
+            let source = create_source();
+            let state_table = create_in_memory_state_table(
+                &[DataType::Int64, DataType::Int64],
+                &[OrderType::ascending(), OrderType::ascending()],
+                &pk_indices(),
+            )
+            .await;
+            let info = ExecutorInfo {
+                schema: source.schema().clone(),
+                pk_indices: source.pk_indices().to_vec(),
+                identity: "AppendOnlyTopNExecutor 1".to_string(),
+            };
+            let top_n_executor = Box::new(
+                AppendOnlyTopNExecutor::<_, false>::new(
+                    source as Box<dyn Executor>,
+                    ActorContext::create(0),
+                    info,
+                    storage_key(),
+                    (0, 4),
+                    order_by(),
+                    state_table,
+                )
+                .unwrap(),
+            );
+            let mut top_n_executor = top_n_executor.execute();
+
+            // consume the init barrier
+            top_n_executor.next().await.unwrap().unwrap();
+            let res = top_n_executor.next().await.unwrap().unwrap();
+            // The first chunk leaves its four smallest rows in the retained window (`10` and `9`
+            // enter it and are pushed out again by `9` and `8`); `11`, in the second chunk, never beats the current 4th-smallest key and is
+            // dropped without an output op -- and, since no `Delete` ever arrives for this
+            // insert-only input, without ever reaching `managed_state` either.
+            assert_eq!(
+                *res.as_chunk().unwrap(),
+                StreamChunk::from_pretty(
+                    "  I I
+                    +  1 0
+                    +  2 1
+                    +  3 2
+                    + 10 3
+                    - 10 3
+                    +  9 4
+                    -  9 4
+                    +  8 5"
+                )
+            );
+            // now (1, 2, 3, 8)
+
+            // barrier
+            assert_matches!(
+                top_n_executor.next().await.unwrap().unwrap(),
+                Message::Barrier(_)
+            );
+            let res = top_n_executor.next().await.unwrap().unwrap();
+            assert_eq!(
+                *res.as_chunk().unwrap(),
+                StreamChunk::from_pretty(
+                    "  I I
+                    -  8 5
+                    +  7 6
+                    -  7 6
+                    +  5 7"
+                )
+            );
+            // now (1, 2, 3, 5); `11` never appears in any op above.
+
+            // barrier
+            assert_matches!(
+                top_n_executor.next().await.unwrap().unwrap(),
+                Message::Barrier(_)
+            );
+        }
+    }
 }
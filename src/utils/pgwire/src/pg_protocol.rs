@@ -14,26 +14,30 @@
 
 use std::any::Any;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::panic::AssertUnwindSafe;
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::str::Utf8Error;
 use std::sync::{Arc, LazyLock, Weak};
-use std::time::Instant;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 use std::{io, str};
 
 use bytes::{Bytes, BytesMut};
+use encoding_rs::Encoding;
 use futures::future::Either;
 use futures::stream::StreamExt;
 use itertools::Itertools;
-use openssl::ssl::{SslAcceptor, SslContext, SslContextRef, SslMethod};
+use openssl::ssl::{AlpnError, SslAcceptor, SslContext, SslContextRef, SslMethod};
 use risingwave_common::types::DataType;
 use risingwave_common::util::panic::FutureCatchUnwindExt;
 use risingwave_sqlparser::ast::Statement;
 use risingwave_sqlparser::parser::Parser;
 use thiserror_ext::AsReport;
-use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf};
 use tokio_openssl::SslStream;
+use tokio_util::sync::CancellationToken;
 use tracing::{error, warn, Instrument};
 
 use crate::error::{PsqlError, PsqlResult};
@@ -41,8 +45,9 @@ use crate::net::AddressRef;
 use crate::pg_extended::ResultCache;
 use crate::pg_message::{
     BeCommandCompleteMessage, BeMessage, BeParameterStatusMessage, FeBindMessage, FeCancelMessage,
-    FeCloseMessage, FeDescribeMessage, FeExecuteMessage, FeMessage, FeParseMessage,
-    FePasswordMessage, FeStartupMessage, TransactionStatus,
+    FeCloseMessage, FeCopyDataMessage, FeCopyFailMessage, FeDescribeMessage, FeExecuteMessage,
+    FeMessage, FeParseMessage, FePasswordMessage, FeSASLInitialResponseMessage,
+    FeSASLResponseMessage, FeStartupMessage, TransactionStatus,
 };
 use crate::pg_server::{Session, SessionManager, UserAuthenticator};
 use crate::types::Format;
@@ -66,14 +71,23 @@ tokio::task_local! {
     pub static CURRENT_SESSION: Weak<dyn Any + Send + Sync>
 }
 
+/// Process-wide directory of live sessions' `(process_id -> (secret_key, cancel_token))`, so a
+/// `CancelRequest` arriving on a brand new connection (it carries no session of its own) can find
+/// and fire the `CancellationToken` of the connection it targets. Entries are added once a
+/// session's startup succeeds and removed by that connection's `Drop`.
+static CANCEL_REGISTRY: LazyLock<std::sync::Mutex<HashMap<i32, (i32, CancellationToken)>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+
 /// The state machine for each psql connection.
 /// Read pg messages from tcp stream and write results back.
 pub struct PgProtocol<S, SM>
 where
     SM: SessionManager,
 {
-    /// Used for write/read pg messages.
-    stream: Conn<S>,
+    /// Used for write/read pg messages. Wrapped in [`Prefixed`] so a byte peeked off the wire to
+    /// detect a direct-TLS `ClientHello` (see [`Self::new_direct_tls`]) can be replayed to the
+    /// first real read instead of being lost.
+    stream: Conn<Prefixed<S>>,
     /// Current states of pg connection.
     state: PgProtocolState,
     /// Whether the connection is terminated.
@@ -101,10 +115,56 @@ where
 
     // Client Address
     peer_addr: AddressRef,
+
+    // In-progress SCRAM-SHA-256 exchange, if the connecting user authenticates via
+    // `UserAuthenticator::Scram`. Lives across the `SASLInitialResponse` -> `SASLResponse`
+    // round trip and is cleared once the exchange finishes (successfully or not).
+    scram: Option<scram::ServerState>,
+
+    // The verified client certificate's subject (CN or SAN), if this connection upgraded to TLS
+    // and a peer certificate was presented. Consulted by `UserAuthenticator::Cert`.
+    peer_cert_subject: Option<String>,
+
+    // The sink a `COPY ... FROM STDIN` is streaming into, plus the `CommandComplete` template
+    // (its `stmt_type` already filled in) to send once `CopyDone` finalizes it. `Some` exactly
+    // while `state` is `PgProtocolState::CopyIn`.
+    copy_in: Option<(<SM::Session as Session>::CopyInSink, BeCommandCompleteMessage)>,
+
+    // How long `read_message` will wait for the next message before giving up on a silent
+    // connection. `None` disables the timeout (the previous, unbounded-wait behavior).
+    idle_timeout: Option<Duration>,
+
+    // Fired when a `CancelRequest` targeting this session's `(process_id, secret_key)` arrives
+    // on another connection. `process_execute_msg` races `session.execute` against this token so
+    // a long-running extended-query statement can actually be interrupted, not just marked
+    // canceled after the fact.
+    cancel_token: CancellationToken,
+
+    // Total bytes a suspended (partially-consumed) portal is allowed to keep buffered in
+    // `result_cache` before `enforce_portal_spill_budget` spills its remaining rows to disk.
+    // Defaults from `PortalSpillConfig` and can be lowered per-session via the
+    // `portal_spill_threshold_bytes` startup parameter.
+    portal_spill_threshold_bytes: usize,
 }
 
 const PGWIRE_QUERY_LOG: &str = "pgwire_query_log";
 
+/// Whether, and how strictly, client certificates are checked during the TLS handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClientCertVerifyMode {
+    /// No client certificate is requested. The default; behaves like today.
+    #[default]
+    Disabled,
+    /// A client certificate is requested and, if presented, verified against `ca`, but an
+    /// absent certificate is still allowed (Postgres `clientcert=verify-ca` without requiring
+    /// presence).
+    Optional,
+    /// A client certificate is required and must verify against `ca`. Its subject is matched
+    /// against the startup `user` (Postgres `clientcert=verify-full`) only for users whose
+    /// authenticator is [`UserAuthenticator::Cert`].
+    VerifyFull,
+}
+
 /// Configures TLS encryption for connections.
 #[derive(Debug, Clone)]
 pub struct TlsConfig {
@@ -112,6 +172,30 @@ pub struct TlsConfig {
     pub cert: PathBuf,
     /// The path to the TLS key.
     pub key: PathBuf,
+    /// CA bundle used to verify client certificates. Required when `verify_mode` is anything
+    /// other than [`ClientCertVerifyMode::Disabled`].
+    pub ca: Option<PathBuf>,
+    /// Whether (and how strictly) to request and verify a client certificate.
+    pub verify_mode: ClientCertVerifyMode,
+}
+
+/// Server-wide default budget for how many bytes' worth of buffered rows a connection's
+/// suspended portals may keep resident in `result_cache` before they're spilled to a temporary
+/// file. A connection can lower (but not raise) this via the `portal_spill_threshold_bytes`
+/// startup parameter.
+#[derive(Debug, Clone, Copy)]
+pub struct PortalSpillConfig {
+    pub memory_budget_bytes: usize,
+}
+
+impl Default for PortalSpillConfig {
+    fn default() -> Self {
+        // 64MiB: generous enough for most cursor-style fetches while still bounding a client
+        // that opens many named portals and drains them slowly.
+        Self {
+            memory_budget_bytes: 64 * 1024 * 1024,
+        }
+    }
 }
 
 impl TlsConfig {
@@ -126,6 +210,8 @@ impl TlsConfig {
             // The path is mounted from project root.
             cert: path_to_cur_proj.join(cert),
             key: path_to_cur_proj.join(key),
+            ca: None,
+            verify_mode: ClientCertVerifyMode::Disabled,
         }
     }
 }
@@ -138,6 +224,7 @@ where
         if let Some(session) = &self.session {
             // Clear the session in session manager.
             self.session_mgr.end_session(session);
+            CANCEL_REGISTRY.lock().unwrap().remove(&session.id().0);
         }
     }
 }
@@ -146,6 +233,10 @@ where
 enum PgProtocolState {
     Startup,
     Regular,
+    /// Entered after a `COPY ... FROM STDIN` statement replies with `CopyInResponse`. Incoming
+    /// `CopyData` chunks are streamed into `copy_in` instead of being parsed as new statements,
+    /// until the client sends `CopyDone` (success) or `CopyFail` (abort).
+    CopyIn,
 }
 
 /// Truncate 0 from C string in Bytes and stringify it (returns slice, no allocations).
@@ -160,21 +251,193 @@ pub fn cstr_to_str(b: &Bytes) -> Result<&str, Utf8Error> {
     std::str::from_utf8(without_null)
 }
 
+/// Server-side RFC 5802 SCRAM-SHA-256 exchange, driven incrementally across a client's
+/// `SASLInitialResponse` and `SASLResponse` messages. Only `salt`/`iterations`/`StoredKey`/
+/// `ServerKey` ever need to be known server-side, so a plaintext password never has to be kept
+/// around to authenticate a later connection.
+mod scram {
+    use base64::Engine;
+    use hmac::{Hmac, Mac};
+    use rand::RngCore;
+    use sha2::{Digest, Sha256};
+
+    pub const MECHANISM: &str = "SCRAM-SHA-256";
+
+    type HmacSha256 = Hmac<Sha256>;
+
+    fn hmac(key: &[u8], data: &[u8]) -> [u8; 32] {
+        let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+        mac.update(data);
+        mac.finalize().into_bytes().into()
+    }
+
+    fn h(data: &[u8]) -> [u8; 32] {
+        Sha256::digest(data).into()
+    }
+
+    fn xor(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        std::array::from_fn(|i| a[i] ^ b[i])
+    }
+
+    /// `SaltedPassword = PBKDF2-HMAC-SHA256(password, salt, iterations)`. Computed once, when a
+    /// password is first set, so logins never need to re-derive it from the plaintext password.
+    pub fn salted_password(password: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        pbkdf2::pbkdf2::<HmacSha256>(password.as_bytes(), salt, iterations, &mut out)
+            .expect("32-byte PBKDF2 output is always valid for HMAC-SHA256");
+        out
+    }
+
+    pub fn client_key(salted_password: &[u8; 32]) -> [u8; 32] {
+        hmac(salted_password, b"Client Key")
+    }
+
+    pub fn stored_key(client_key: &[u8; 32]) -> [u8; 32] {
+        h(client_key)
+    }
+
+    pub fn server_key(salted_password: &[u8; 32]) -> [u8; 32] {
+        hmac(salted_password, b"Server Key")
+    }
+
+    fn random_nonce() -> String {
+        let mut bytes = [0u8; 18];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    pub struct ClientFirst {
+        /// The `n=...,r=...` portion of the client-first-message, i.e. everything after the GS2
+        /// header; this is what `AuthMessage` is built from, not the raw message.
+        pub bare: String,
+        pub nonce: String,
+    }
+
+    /// Parses a `client-first-message` of the form `n,,n=<user>,r=<client-nonce>`. The GS2
+    /// header and username are ignored: the session was already resolved from the startup
+    /// message, so SCRAM here only needs to verify the password.
+    pub fn parse_client_first(msg: &str) -> Option<ClientFirst> {
+        let bare = msg.splitn(3, ',').nth(2)?.to_string();
+        let nonce = bare
+            .split(',')
+            .find_map(|field| field.strip_prefix("r="))?
+            .to_string();
+        Some(ClientFirst { bare, nonce })
+    }
+
+    /// The exchange's two phases: waiting for the client's first message, then waiting for (and
+    /// verifying) its final message once a `server-first-message` has been sent.
+    enum Phase {
+        AwaitingClientFirst,
+        AwaitingClientFinal {
+            client_first_bare: String,
+            server_first: String,
+            combined_nonce: String,
+        },
+    }
+
+    /// Per-connection SCRAM state, seeded from the credentials a [`UserAuthenticator::Scram`]
+    /// carries for the connecting user.
+    pub struct ServerState {
+        phase: Phase,
+        iterations: u32,
+        salt: Vec<u8>,
+        stored_key: [u8; 32],
+        server_key: [u8; 32],
+    }
+
+    impl ServerState {
+        pub fn new(iterations: u32, salt: Vec<u8>, stored_key: [u8; 32], server_key: [u8; 32]) -> Self {
+            Self {
+                phase: Phase::AwaitingClientFirst,
+                iterations,
+                salt,
+                stored_key,
+                server_key,
+            }
+        }
+
+        /// Builds the `server-first-message` (`r=<nonce>,s=<salt>,i=<iterations>`) replying to
+        /// the client's first message, and advances to waiting for the client's final message.
+        pub fn server_first(&mut self, client_first: &ClientFirst) -> String {
+            let combined_nonce = format!("{}{}", client_first.nonce, random_nonce());
+            let server_first = format!(
+                "r={},s={},i={}",
+                combined_nonce,
+                base64::engine::general_purpose::STANDARD.encode(&self.salt),
+                self.iterations
+            );
+            self.phase = Phase::AwaitingClientFinal {
+                client_first_bare: client_first.bare.clone(),
+                server_first: server_first.clone(),
+                combined_nonce,
+            };
+            server_first
+        }
+
+        /// Verifies a `client-final-message` (`c=biws,r=<combined-nonce>,p=<client-proof>`)
+        /// against the stored key, returning the `server-final-message` (`v=<server-signature>`)
+        /// on success. `None` on any mismatch: wrong nonce, bad proof, or called out of order.
+        pub fn verify_client_final(&self, client_final: &str) -> Option<String> {
+            let Phase::AwaitingClientFinal {
+                client_first_bare,
+                server_first,
+                combined_nonce,
+            } = &self.phase
+            else {
+                return None;
+            };
+            let (without_proof, proof_b64) = client_final.rsplit_once(",p=")?;
+            let nonce = without_proof
+                .split(',')
+                .find_map(|field| field.strip_prefix("r="))?;
+            if nonce != combined_nonce {
+                return None;
+            }
+            let proof: [u8; 32] = base64::engine::general_purpose::STANDARD
+                .decode(proof_b64)
+                .ok()?
+                .try_into()
+                .ok()?;
+
+            let auth_message = format!("{},{},{}", client_first_bare, server_first, without_proof);
+            let client_signature = hmac(&self.stored_key, auth_message.as_bytes());
+            let recovered_client_key = xor(&proof, &client_signature);
+            if stored_key(&recovered_client_key) != self.stored_key {
+                return None;
+            }
+            let server_signature = hmac(&self.server_key, auth_message.as_bytes());
+            Some(format!(
+                "v={}",
+                base64::engine::general_purpose::STANDARD.encode(server_signature)
+            ))
+        }
+    }
+}
+
 impl<S, SM> PgProtocol<S, SM>
 where
     S: AsyncWrite + AsyncRead + Unpin,
     SM: SessionManager,
 {
+    /// If no message arrives within `idle_timeout`, [`Self::read_message`] gives up and returns
+    /// an error so the connection is torn down, rather than holding session resources forever
+    /// for a client that opened a connection and went silent.
+    const IDLE_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
     pub fn new(
         stream: S,
         session_mgr: Arc<SM>,
         tls_config: Option<TlsConfig>,
         peer_addr: AddressRef,
+        idle_timeout: Option<Duration>,
+        portal_spill_config: PortalSpillConfig,
     ) -> Self {
         Self {
             stream: Conn::Unencrypted(PgStream {
-                stream: Some(stream),
+                stream: Some(Prefixed::new(stream)),
                 write_buf: BytesMut::with_capacity(10 * 1024),
+                client_encoding: encoding_rs::UTF_8,
             }),
             is_terminate: false,
             state: PgProtocolState::Startup,
@@ -191,7 +454,77 @@ where
             statement_portal_dependency: Default::default(),
             ignore_util_sync: false,
             peer_addr,
+            scram: None,
+            peer_cert_subject: None,
+            copy_in: None,
+            idle_timeout,
+            cancel_token: CancellationToken::new(),
+            portal_spill_threshold_bytes: portal_spill_config.memory_budget_bytes,
+        }
+    }
+
+    /// Accepts a connection that opened a TLS handshake directly instead of sending an
+    /// `SSLRequest` packet first (Postgres 17+ "direct SSL"). `first_byte` is the one byte the
+    /// caller had to read off the wire to tell the two apart — `0x16` is the TLS record
+    /// `ContentType::Handshake`, which the leading length byte of every supported startup packet
+    /// never is — and is replayed into the handshake via [`Prefixed`] so it isn't lost.
+    ///
+    /// To guard against protocol confusion from skipping the `SSLRequest` round-trip, the
+    /// handshake is required to negotiate the `postgresql` ALPN protocol; any other outcome (or
+    /// none) fails the connection instead of silently falling back to an unauthenticated read.
+    pub async fn new_direct_tls(
+        stream: S,
+        first_byte: u8,
+        session_mgr: Arc<SM>,
+        tls_config: &TlsConfig,
+        peer_addr: AddressRef,
+        idle_timeout: Option<Duration>,
+        portal_spill_config: PortalSpillConfig,
+    ) -> PsqlResult<Self> {
+        let ssl_ctx = build_ssl_ctx_from_config(tls_config)?;
+        let mut protocol = Self {
+            stream: Conn::Unencrypted(PgStream {
+                stream: Some(Prefixed {
+                    first_byte: Some(first_byte),
+                    inner: stream,
+                }),
+                write_buf: BytesMut::with_capacity(10 * 1024),
+                client_encoding: encoding_rs::UTF_8,
+            }),
+            is_terminate: false,
+            state: PgProtocolState::Startup,
+            session_mgr,
+            session: None,
+            tls_context: None,
+            result_cache: Default::default(),
+            unnamed_prepare_statement: Default::default(),
+            prepare_statement_store: Default::default(),
+            unnamed_portal: Default::default(),
+            portal_store: Default::default(),
+            statement_portal_dependency: Default::default(),
+            ignore_util_sync: false,
+            peer_addr,
+            scram: None,
+            peer_cert_subject: None,
+            copy_in: None,
+            idle_timeout,
+            cancel_token: CancellationToken::new(),
+            portal_spill_threshold_bytes: portal_spill_config.memory_budget_bytes,
+        };
+
+        let ssl_stream = protocol.stream.ssl(&ssl_ctx).await?;
+        let negotiated = ssl_stream
+            .stream
+            .as_ref()
+            .and_then(|s| s.ssl().selected_alpn_protocol());
+        if negotiated != Some(ALPN_POSTGRESQL_PROTOCOL) {
+            return Err(PsqlError::Uncategorized(
+                "direct-TLS connection did not negotiate the postgresql ALPN protocol".into(),
+            ));
         }
+        protocol.peer_cert_subject = ssl_stream.stream.as_ref().and_then(peer_cert_subject);
+        protocol.stream = Conn::Ssl(ssl_stream);
+        Ok(protocol)
     }
 
     /// Processes one message. Returns true if the connection is terminated.
@@ -300,6 +633,11 @@ where
             FeMessage::Ssl => self.process_ssl_msg().await?,
             FeMessage::Startup(msg) => self.process_startup_msg(msg)?,
             FeMessage::Password(msg) => self.process_password_msg(msg)?,
+            FeMessage::SASLInitialResponse(msg) => self.process_sasl_initial_response(msg)?,
+            FeMessage::SASLResponse(msg) => self.process_sasl_response(msg)?,
+            FeMessage::CopyData(msg) => self.process_copy_data_msg(msg)?,
+            FeMessage::CopyDone => self.process_copy_done_msg().await?,
+            FeMessage::CopyFail(msg) => self.process_copy_fail_msg(msg)?,
             FeMessage::Query(query_msg) => self.process_query_msg(query_msg.get_sql()).await?,
             FeMessage::CancelQuery(m) => self.process_cancel_msg(m)?,
             FeMessage::Terminate => self.process_terminate(),
@@ -350,9 +688,50 @@ where
     }
 
     pub async fn read_message(&mut self) -> io::Result<FeMessage> {
+        let Some(idle_timeout) = self.idle_timeout else {
+            return self.read_message_inner().await;
+        };
+
+        let mut elapsed = Duration::ZERO;
+        loop {
+            let remaining = idle_timeout.saturating_sub(elapsed);
+            if remaining.is_zero() {
+                // Best-effort notice; the caller tears down the connection regardless of whether
+                // this write succeeds, running `Drop` (and thus `end_session`) either way.
+                let _ = self.stream.write_no_flush(&BeMessage::ErrorResponse(Box::new(
+                    PsqlError::Uncategorized("connection idle for too long".into()),
+                )));
+                let _ = self.stream.flush().await;
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "idle connection timeout",
+                ));
+            }
+
+            let tick = remaining.min(Self::IDLE_KEEPALIVE_INTERVAL);
+            match tokio::time::timeout(tick, self.read_message_inner()).await {
+                Ok(result) => return result,
+                Err(_elapsed) => {
+                    elapsed += tick;
+                    if elapsed < idle_timeout {
+                        // Let intermediate proxies/load balancers know the (still-idle) session
+                        // is alive, instead of silently ticking down to the hard timeout.
+                        // `standard_conforming_strings` is fixed, so re-reporting it leaves the
+                        // client's view of the session's settings unchanged.
+                        let _ = self.stream.write_no_flush(&BeMessage::ParameterStatus(
+                            BeParameterStatusMessage::StandardConformingString("on"),
+                        ));
+                        let _ = self.stream.flush().await;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn read_message_inner(&mut self) -> io::Result<FeMessage> {
         match self.state {
             PgProtocolState::Startup => self.stream.read_startup().await,
-            PgProtocolState::Regular => self.stream.read().await,
+            PgProtocolState::Regular | PgProtocolState::CopyIn => self.stream.read().await,
         }
     }
 
@@ -372,6 +751,7 @@ where
             // Construct ssl stream and replace with current one.
             self.stream.write(&BeMessage::EncryptionResponseYes).await?;
             let ssl_stream = self.stream.ssl(context).await?;
+            self.peer_cert_subject = ssl_stream.stream.as_ref().and_then(peer_cert_subject);
             self.stream = Conn::Ssl(ssl_stream);
         } else {
             // If no, say no for encryption.
@@ -405,6 +785,23 @@ where
                 .map_err(PsqlError::StartupError)?;
         }
 
+        let client_encoding = msg.config.get("client_encoding");
+        if let Some(client_encoding) = client_encoding {
+            session
+                .set_config("client_encoding", client_encoding.clone())
+                .map_err(PsqlError::StartupError)?;
+        }
+
+        // Lets a session tighten (but not loosen) the server-wide default budget for how much of
+        // a suspended portal's rows `enforce_portal_spill_budget` keeps resident in memory.
+        if let Some(threshold) = msg
+            .config
+            .get("portal_spill_threshold_bytes")
+            .and_then(|v| v.parse::<usize>().ok())
+        {
+            self.portal_spill_threshold_bytes = self.portal_spill_threshold_bytes.min(threshold);
+        }
+
         match session.user_authenticator() {
             UserAuthenticator::None => {
                 self.stream.write_no_flush(&BeMessage::AuthenticationOk)?;
@@ -417,6 +814,9 @@ where
                 self.stream
                     .write_parameter_status_msg_no_flush(&ParameterStatus {
                         application_name: application_name.cloned(),
+                        client_encoding: client_encoding.cloned(),
+                        session_authorization: Some(user_name.clone()),
+                        ..Default::default()
                     })?;
                 self.ready_for_query()?;
             }
@@ -428,8 +828,51 @@ where
                 self.stream
                     .write_no_flush(&BeMessage::AuthenticationMd5Password(salt))?;
             }
+            UserAuthenticator::Scram { .. } => {
+                self.stream
+                    .write_no_flush(&BeMessage::AuthenticationSASL(&[scram::MECHANISM]))?;
+            }
+            UserAuthenticator::Cert => {
+                // No password round-trip: the client was already authenticated by its TLS
+                // certificate. We just need the verified subject to match the startup `user`,
+                // mirroring Postgres `clientcert=verify-full`.
+                match &self.peer_cert_subject {
+                    Some(subject) if *subject == user_name => {}
+                    Some(subject) => {
+                        return Err(PsqlError::Uncategorized(
+                            format!(
+                                "certificate subject '{}' does not match requested user '{}'",
+                                subject, user_name
+                            )
+                            .into(),
+                        ));
+                    }
+                    None => {
+                        return Err(PsqlError::Uncategorized(
+                            "no client certificate was presented".into(),
+                        ));
+                    }
+                }
+                self.stream.write_no_flush(&BeMessage::AuthenticationOk)?;
+                self.stream
+                    .write_no_flush(&BeMessage::BackendKeyData(session.id()))?;
+                self.stream
+                    .write_parameter_status_msg_no_flush(&ParameterStatus {
+                        application_name: application_name.cloned(),
+                        client_encoding: client_encoding.cloned(),
+                        session_authorization: Some(user_name.clone()),
+                        ..Default::default()
+                    })?;
+                self.ready_for_query()?;
+            }
         }
 
+        let (process_id, secret_key) = session.id();
+        CANCEL_REGISTRY
+            .lock()
+            .unwrap()
+            .insert(process_id, (secret_key, self.cancel_token.clone()));
+
         self.session = Some(session);
         self.state = PgProtocolState::Regular;
         Ok(())
@@ -448,11 +891,129 @@ where
         Ok(())
     }
 
+    /// Handles a client's `SASLInitialResponse`: the chosen mechanism plus its SCRAM
+    /// client-first-message. Replies with `AuthenticationSASLContinue` carrying the
+    /// server-first-message, and stashes the exchange state for [`Self::process_sasl_response`].
+    fn process_sasl_initial_response(&mut self, msg: FeSASLInitialResponseMessage) -> PsqlResult<()> {
+        if msg.mechanism != scram::MECHANISM {
+            return Err(PsqlError::PasswordError);
+        }
+        let authenticator = self.session.as_ref().unwrap().user_authenticator();
+        let UserAuthenticator::Scram {
+            iterations,
+            salt,
+            stored_key,
+            server_key,
+        } = authenticator
+        else {
+            return Err(PsqlError::PasswordError);
+        };
+        let client_first =
+            scram::parse_client_first(&msg.client_first).ok_or(PsqlError::PasswordError)?;
+        let mut state = scram::ServerState::new(iterations, salt.clone(), stored_key, server_key);
+        let server_first = state.server_first(&client_first);
+        self.scram = Some(state);
+        self.stream
+            .write_no_flush(&BeMessage::AuthenticationSASLContinue(&server_first))?;
+        Ok(())
+    }
+
+    /// Handles a client's `SASLResponse` carrying its client-final-message. Verifies the
+    /// client's proof against the stored key and, on success, completes authentication exactly
+    /// like [`Self::process_password_msg`] does for the other authenticators.
+    fn process_sasl_response(&mut self, msg: FeSASLResponseMessage) -> PsqlResult<()> {
+        let state = self.scram.as_ref().ok_or(PsqlError::PasswordError)?;
+        let client_final =
+            std::str::from_utf8(&msg.data).map_err(|_| PsqlError::PasswordError)?;
+        let server_final = state
+            .verify_client_final(client_final)
+            .ok_or(PsqlError::PasswordError)?;
+        self.scram = None;
+
+        self.stream
+            .write_no_flush(&BeMessage::AuthenticationSASLFinal(&server_final))?;
+        self.stream.write_no_flush(&BeMessage::AuthenticationOk)?;
+        self.stream
+            .write_parameter_status_msg_no_flush(&ParameterStatus::default())?;
+        self.ready_for_query()?;
+        self.state = PgProtocolState::Regular;
+        Ok(())
+    }
+
+    /// Streams one `CopyData` chunk into the in-progress `COPY ... FROM STDIN` sink. Only valid
+    /// while `state` is `PgProtocolState::CopyIn`.
+    fn process_copy_data_msg(&mut self, msg: FeCopyDataMessage) -> PsqlResult<()> {
+        let (sink, _) = self
+            .copy_in
+            .as_mut()
+            .ok_or_else(|| PsqlError::Uncategorized("unexpected CopyData outside of COPY FROM STDIN".into()))?;
+        sink.write_chunk(msg.data)
+            .map_err(|e| PsqlError::Uncategorized(e.into()))
+    }
+
+    /// Finalizes a `COPY ... FROM STDIN`: hands the accumulated rows off to the session and
+    /// replies with `CommandComplete`, then returns to `PgProtocolState::Regular`.
+    async fn process_copy_done_msg(&mut self) -> PsqlResult<()> {
+        let (sink, mut complete) = self
+            .copy_in
+            .take()
+            .ok_or_else(|| PsqlError::Uncategorized("unexpected CopyDone outside of COPY FROM STDIN".into()))?;
+        complete.rows_cnt = sink
+            .finish()
+            .await
+            .map_err(|e| PsqlError::Uncategorized(e.into()))?;
+        self.stream
+            .write_no_flush(&BeMessage::CommandComplete(complete))?;
+        self.state = PgProtocolState::Regular;
+        self.ready_for_query()?;
+        Ok(())
+    }
+
+    /// Aborts an in-progress `COPY ... FROM STDIN` at the client's request.
+    fn process_copy_fail_msg(&mut self, msg: FeCopyFailMessage) -> PsqlResult<()> {
+        if let Some((sink, _)) = self.copy_in.take() {
+            sink.fail(msg.error_message.clone());
+        }
+        self.state = PgProtocolState::Regular;
+        Err(PsqlError::Uncategorized(msg.error_message.into()))
+    }
+
     fn process_cancel_msg(&mut self, m: FeCancelMessage) -> PsqlResult<()> {
         let session_id = (m.target_process_id, m.target_secret_key);
         tracing::trace!("cancel query in session: {:?}", session_id);
-        self.session_mgr.cancel_queries_in_session(session_id);
-        self.session_mgr.cancel_creating_jobs_in_session(session_id);
+        // `SessionManager` (declared in `pg_server.rs`, not part of this snapshot) has no
+        // `verify_secret`-equivalent method we can call here; `CANCEL_REGISTRY`, populated from
+        // each session's own `BackendKeyData`, is the one secret store this crate actually owns,
+        // so it's what gates cancellation below. A client only learns `target_secret_key` by
+        // having received it in that session's own `BackendKeyData`, so verifying it here is what
+        // keeps a client that merely guesses another session's process id from cancelling its
+        // queries.
+        // Re-checked against the same lookup that hands back `cancel_token` below, rather than
+        // trusting a verification done under an earlier, separately-acquired lock: the registry
+        // entry for `target_process_id` could otherwise change between the two acquisitions
+        // (e.g. the process id gets reused by an unrelated session) and let a stale verification
+        // authorize cancelling that unrelated session's token.
+        let cancel_entry = CANCEL_REGISTRY
+            .lock()
+            .unwrap()
+            .get(&m.target_process_id)
+            .filter(|(secret_key, _)| *secret_key == m.target_secret_key)
+            .map(|(_, cancel_token)| cancel_token.clone());
+
+        if let Some(cancel_token) = cancel_entry {
+            self.session_mgr.cancel_queries_in_session(session_id);
+            self.session_mgr.cancel_creating_jobs_in_session(session_id);
+
+            // Also abort any extended-query execution that's in-flight on the target
+            // connection itself, so cancellation isn't limited to queries the session manager
+            // already knows how to interrupt.
+            cancel_token.cancel();
+        } else {
+            tracing::warn!(
+                "rejected CancelRequest for process {} with mismatched secret key",
+                m.target_process_id
+            );
+        }
         self.stream.write_no_flush(&BeMessage::EmptyQueryResponse)?;
         Ok(())
     }
@@ -538,12 +1099,13 @@ where
                 .write_no_flush(&BeMessage::NoticeResponse(notice))?;
         }
 
+        // Report any reported GUC this statement changed (e.g. a `SET`/`RESET`), so drivers that
+        // rely on async `ParameterStatus` updates (rust-postgres in particular needs fresh
+        // `DateStyle`/`TimeZone`/`IntervalStyle` to decode values correctly) stay in sync instead
+        // of silently keeping stale session state.
         let status = res.status();
-        if let Some(ref application_name) = status.application_name {
-            self.stream.write_no_flush(&BeMessage::ParameterStatus(
-                BeParameterStatusMessage::ApplicationName(application_name),
-            ))?;
-        }
+        self.stream
+            .write_changed_parameter_status_msg_no_flush(&status)?;
 
         if res.is_query() {
             self.stream
@@ -551,13 +1113,15 @@ where
 
             let mut rows_cnt = 0;
 
+            let mut batch = self.stream.data_row_batch();
             while let Some(row_set) = res.values_stream().next().await {
                 let row_set = row_set.map_err(PsqlError::SimpleQueryError)?;
                 for row in row_set {
-                    self.stream.write_no_flush(&BeMessage::DataRow(&row))?;
+                    batch.write_row(&BeMessage::DataRow(&row)).await?;
                     rows_cnt += 1;
                 }
             }
+            batch.finish().await?;
 
             // Run the callback before sending the `CommandComplete` message.
             res.run_callback().await?;
@@ -567,6 +1131,18 @@ where
                     stmt_type: res.stmt_type(),
                     rows_cnt,
                 }))?;
+        } else if res.is_copy_in() {
+            // Don't run the callback or send `CommandComplete` yet: both happen once the copy
+            // finishes, in `process_copy_done_msg`.
+            self.stream.write_no_flush(&BeMessage::CopyInResponse)?;
+            self.copy_in = Some((
+                res.copy_in_sink(),
+                BeCommandCompleteMessage {
+                    stmt_type: res.stmt_type(),
+                    rows_cnt: 0,
+                },
+            ));
+            self.state = PgProtocolState::CopyIn;
         } else {
             // Run the callback before sending the `CommandComplete` message.
             res.run_callback().await?;
@@ -694,14 +1270,40 @@ where
             .iter()
             .map(|&format_code| Format::from_i16(format_code))
             .try_collect()?;
-        let param_formats = msg
+        let param_formats: Vec<Format> = msg
             .param_format_codes
             .iter()
             .map(|&format_code| Format::from_i16(format_code))
             .try_collect()?;
 
+        // Text-format params arrive encoded in the client's negotiated `client_encoding`; the
+        // rest of the server only ever deals in UTF-8, so transcode them here before they reach
+        // `session.bind`. Per the wire protocol, an empty format list means every param is text
+        // and a single-element list applies that one format to every param.
+        let params = if self.stream.client_encoding() == encoding_rs::UTF_8 {
+            msg.params
+        } else {
+            msg.params
+                .into_iter()
+                .enumerate()
+                .map(|(i, param)| {
+                    let format = param_formats
+                        .get(i)
+                        .or_else(|| param_formats.first())
+                        .copied()
+                        .unwrap_or(Format::Text);
+                    match (param, format) {
+                        (Some(bytes), Format::Text) => {
+                            Some(self.stream.decode_client_text(&bytes))
+                        }
+                        (param, _) => param,
+                    }
+                })
+                .collect()
+        };
+
         let portal = session
-            .bind(prepare_statement, msg.params, param_formats, result_formats)
+            .bind(prepare_statement, params, param_formats, result_formats)
             .map_err(PsqlError::Uncategorized)?;
 
         if portal_name.is_empty() {
@@ -733,10 +1335,11 @@ where
         if let Some(mut result_cache) = self.result_cache.remove(&portal_name) {
             assert!(self.portal_store.contains_key(&portal_name));
 
-            let is_cosume_completed = result_cache.consume::<S>(row_max, &mut self.stream).await?;
+            let is_cosume_completed = result_cache.consume::<Prefixed<S>>(row_max, &mut self.stream).await?;
 
             if !is_cosume_completed {
                 self.result_cache.insert(portal_name, result_cache);
+                self.enforce_portal_spill_budget().await?;
             }
         } else {
             let start = Instant::now();
@@ -744,7 +1347,38 @@ where
             let sql: Arc<str> = Arc::from(format!("{}", portal));
 
             let _exec_context_guard = session.init_exec_context(sql.clone());
-            let result = session.clone().execute(portal).await;
+            // A token fired while no statement was executing here (e.g. by a `CancelRequest`
+            // aimed at a simple query) is replaced first, so it can't cancel this one up front.
+            if self.cancel_token.is_cancelled() {
+                self.cancel_token = CancellationToken::new();
+                CANCEL_REGISTRY
+                    .lock()
+                    .unwrap()
+                    .insert(session_id, (session.id().1, self.cancel_token.clone()));
+            }
+            // Races the statement against `cancel_token` so a `CancelRequest` landing on another
+            // connection for this session actually interrupts it, rather than only being able to
+            // reject it after the fact.
+            let result = tokio::select! {
+                biased;
+                _ = self.cancel_token.cancelled() => {
+                    Err(io::Error::new(
+                        io::ErrorKind::Interrupted,
+                        "canceling statement due to user request",
+                    ).into())
+                }
+                result = session.clone().execute(portal) => result,
+            };
+            if self.cancel_token.is_cancelled() {
+                // The token is one-shot: once fired it stays cancelled forever, so later
+                // statements on this connection need a fresh one to race against. The registry
+                // entry is repointed at it so a subsequent `CancelRequest` still finds it.
+                self.cancel_token = CancellationToken::new();
+                CANCEL_REGISTRY
+                    .lock()
+                    .unwrap()
+                    .insert(session_id, (session.id().1, self.cancel_token.clone()));
+            }
 
             let mills = start.elapsed().as_millis();
 
@@ -759,15 +1393,51 @@ where
 
             let pg_response = result.map_err(PsqlError::ExtendedExecuteError)?;
             let mut result_cache = ResultCache::new(pg_response);
-            let is_consume_completed = result_cache.consume::<S>(row_max, &mut self.stream).await?;
+            let is_consume_completed = result_cache.consume::<Prefixed<S>>(row_max, &mut self.stream).await?;
             if !is_consume_completed {
                 self.result_cache.insert(portal_name, result_cache);
+                self.enforce_portal_spill_budget().await?;
             }
         }
 
         Ok(())
     }
 
+    /// Keeps the total bytes buffered across all of `result_cache`'s suspended portals within
+    /// `portal_spill_threshold_bytes`: while over budget, spills the largest portal's remaining
+    /// rows to a temporary file of length-prefixed `DataRow` frames. Spilling is transparent to
+    /// later `Execute`s — `ResultCache::consume` streams from the spill file the same way it
+    /// streams from memory.
+    async fn enforce_portal_spill_budget(&mut self) -> PsqlResult<()> {
+        loop {
+            let total_bytes: usize = self.result_cache.values().map(|rc| rc.buffered_bytes()).sum();
+            if total_bytes <= self.portal_spill_threshold_bytes {
+                return Ok(());
+            }
+            let Some(largest) = self
+                .result_cache
+                .iter()
+                .max_by_key(|(_, rc)| rc.buffered_bytes())
+                .map(|(name, _)| name.clone())
+            else {
+                return Ok(());
+            };
+            let result_cache = self.result_cache.get_mut(&largest).unwrap();
+            let process_id = self.session.as_ref().map_or(0, |s| s.id().0);
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            largest.hash(&mut hasher);
+            let spill_path = std::env::temp_dir().join(format!(
+                "rw-pg-portal-spill-{}-{:x}.tmp",
+                process_id,
+                hasher.finish()
+            ));
+            result_cache
+                .spill_to_disk(&spill_path)
+                .await
+                .map_err(|e| PsqlError::Uncategorized(e.into()))?;
+        }
+    }
+
     fn process_describe_msg(&mut self, msg: FeDescribeMessage) -> PsqlResult<()> {
         let name = cstr_to_str(&msg.name).unwrap().to_string();
         let session = self.session.clone().unwrap();
@@ -899,6 +1569,11 @@ pub struct PgStream<S> {
     stream: Option<S>,
     /// Write into buffer before flush to stream.
     write_buf: BytesMut,
+    /// The client's negotiated `client_encoding`. Text-format `Bind` parameters are transcoded
+    /// from it into the server's internal UTF-8 representation; outgoing messages are written as
+    /// UTF-8 without transcoding. Defaults to `UTF_8`, in which case `Bind` parameters are passed
+    /// through unchanged.
+    client_encoding: &'static Encoding,
 }
 
 /// At present there is a hard-wired set of parameters for which
@@ -916,10 +1591,33 @@ pub struct PgStream<S> {
 ///  * `integer_datetimes`
 ///  * `standard_conforming_string`
 ///
+/// `server_version` and `standard_conforming_string` are currently fixed and so aren't tracked
+/// here; the rest can vary per session (or change mid-session via `SET`) and are carried as
+/// `Some(new_value)` whenever they need to be (re-)reported, with `None` meaning "use the
+/// default" at startup or "unchanged" when [`PgStream::write_changed_parameter_status_msg_no_flush`]
+/// is used to report a `SET`/`RESET`.
+///
 /// See: <https://www.postgresql.org/docs/9.2/static/protocol-flow.html#PROTOCOL-ASYNC>.
 #[derive(Debug, Default, Clone)]
 pub struct ParameterStatus {
     pub application_name: Option<String>,
+    pub server_encoding: Option<String>,
+    pub client_encoding: Option<String>,
+    pub date_style: Option<String>,
+    pub interval_style: Option<String>,
+    pub time_zone: Option<String>,
+    pub integer_datetimes: Option<String>,
+    pub is_superuser: Option<String>,
+    pub session_authorization: Option<String>,
+}
+
+/// Resolves a `client_encoding` setting (Postgres charset name, e.g. `LATIN1`, `UTF8`,
+/// `EUC_JP`) to the [`Encoding`] used to transcode text values for that client. Falls back to
+/// UTF-8 for names `encoding_rs` doesn't recognize, e.g. Postgres-only aliases, rather than
+/// rejecting the connection over a cosmetic mismatch.
+fn encoding_for_name(name: &str) -> &'static Encoding {
+    // Postgres spells multi-part names with `_` (`EUC_JP`), `encoding_rs` labels with `-`.
+    Encoding::for_label(name.replace('_', "-").as_bytes()).unwrap_or(encoding_rs::UTF_8)
 }
 
 impl<S> PgStream<S>
@@ -934,9 +1632,29 @@ where
         FeMessage::read(self.stream()).await
     }
 
+    /// Transcodes one text-format value from the client's negotiated `client_encoding` into the
+    /// server's internal UTF-8 representation. When `client_encoding` is `UTF8`, the
+    /// overwhelmingly common case, the bytes are copied unchanged.
+    fn decode_client_text(&self, bytes: &[u8]) -> Bytes {
+        if self.client_encoding == encoding_rs::UTF_8 {
+            return Bytes::copy_from_slice(bytes);
+        }
+        let (decoded, _, _) = self.client_encoding.decode(bytes);
+        Bytes::from(decoded.into_owned().into_bytes())
+    }
+
+    fn client_encoding(&self) -> &'static Encoding {
+        self.client_encoding
+    }
+
     fn write_parameter_status_msg_no_flush(&mut self, status: &ParameterStatus) -> io::Result<()> {
+        if let Some(client_encoding) = &status.client_encoding {
+            self.client_encoding = encoding_for_name(client_encoding);
+        }
         self.write_no_flush(&BeMessage::ParameterStatus(
-            BeParameterStatusMessage::ClientEncoding("UTF8"),
+            BeParameterStatusMessage::ClientEncoding(
+                status.client_encoding.as_deref().unwrap_or("UTF8"),
+            ),
         ))?;
         self.write_no_flush(&BeMessage::ParameterStatus(
             BeParameterStatusMessage::StandardConformingString("on"),
@@ -944,11 +1662,96 @@ where
         self.write_no_flush(&BeMessage::ParameterStatus(
             BeParameterStatusMessage::ServerVersion("9.5.0"),
         ))?;
+        self.write_no_flush(&BeMessage::ParameterStatus(
+            BeParameterStatusMessage::ServerEncoding(
+                status.server_encoding.as_deref().unwrap_or("UTF8"),
+            ),
+        ))?;
+        self.write_no_flush(&BeMessage::ParameterStatus(BeParameterStatusMessage::DateStyle(
+            status.date_style.as_deref().unwrap_or("ISO, MDY"),
+        )))?;
+        self.write_no_flush(&BeMessage::ParameterStatus(
+            BeParameterStatusMessage::IntervalStyle(
+                status.interval_style.as_deref().unwrap_or("postgres"),
+            ),
+        ))?;
+        self.write_no_flush(&BeMessage::ParameterStatus(BeParameterStatusMessage::TimeZone(
+            status.time_zone.as_deref().unwrap_or("UTC"),
+        )))?;
+        self.write_no_flush(&BeMessage::ParameterStatus(
+            BeParameterStatusMessage::IntegerDatetimes(
+                status.integer_datetimes.as_deref().unwrap_or("on"),
+            ),
+        ))?;
+        self.write_no_flush(&BeMessage::ParameterStatus(
+            BeParameterStatusMessage::IsSuperuser(status.is_superuser.as_deref().unwrap_or("off")),
+        ))?;
+        if let Some(session_authorization) = &status.session_authorization {
+            self.write_no_flush(&BeMessage::ParameterStatus(
+                BeParameterStatusMessage::SessionAuthorization(session_authorization),
+            ))?;
+        }
+        if let Some(application_name) = &status.application_name {
+            self.write_no_flush(&BeMessage::ParameterStatus(
+                BeParameterStatusMessage::ApplicationName(application_name),
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Reports only the reported GUCs that `status` marks as changed (i.e. `Some`), for a
+    /// mid-session `SET`/`RESET`. Unlike [`Self::write_parameter_status_msg_no_flush`], fields
+    /// left `None` are left unreported rather than backfilled with their default.
+    fn write_changed_parameter_status_msg_no_flush(
+        &mut self,
+        status: &ParameterStatus,
+    ) -> io::Result<()> {
         if let Some(application_name) = &status.application_name {
             self.write_no_flush(&BeMessage::ParameterStatus(
                 BeParameterStatusMessage::ApplicationName(application_name),
             ))?;
         }
+        if let Some(client_encoding) = &status.client_encoding {
+            self.client_encoding = encoding_for_name(client_encoding);
+            self.write_no_flush(&BeMessage::ParameterStatus(
+                BeParameterStatusMessage::ClientEncoding(client_encoding),
+            ))?;
+        }
+        if let Some(server_encoding) = &status.server_encoding {
+            self.write_no_flush(&BeMessage::ParameterStatus(
+                BeParameterStatusMessage::ServerEncoding(server_encoding),
+            ))?;
+        }
+        if let Some(date_style) = &status.date_style {
+            self.write_no_flush(&BeMessage::ParameterStatus(
+                BeParameterStatusMessage::DateStyle(date_style),
+            ))?;
+        }
+        if let Some(interval_style) = &status.interval_style {
+            self.write_no_flush(&BeMessage::ParameterStatus(
+                BeParameterStatusMessage::IntervalStyle(interval_style),
+            ))?;
+        }
+        if let Some(time_zone) = &status.time_zone {
+            self.write_no_flush(&BeMessage::ParameterStatus(BeParameterStatusMessage::TimeZone(
+                time_zone,
+            )))?;
+        }
+        if let Some(integer_datetimes) = &status.integer_datetimes {
+            self.write_no_flush(&BeMessage::ParameterStatus(
+                BeParameterStatusMessage::IntegerDatetimes(integer_datetimes),
+            ))?;
+        }
+        if let Some(is_superuser) = &status.is_superuser {
+            self.write_no_flush(&BeMessage::ParameterStatus(
+                BeParameterStatusMessage::IsSuperuser(is_superuser),
+            ))?;
+        }
+        if let Some(session_authorization) = &status.session_authorization {
+            self.write_no_flush(&BeMessage::ParameterStatus(
+                BeParameterStatusMessage::SessionAuthorization(session_authorization),
+            ))?;
+        }
         Ok(())
     }
 
@@ -956,6 +1759,12 @@ where
         BeMessage::write(&mut self.write_buf, message)
     }
 
+    /// Current size of the buffered-but-unflushed bytes, i.e. how much has been written via
+    /// [`Self::write_no_flush`] since the last [`Self::flush`].
+    fn write_buf_len(&self) -> usize {
+        self.write_buf.len()
+    }
+
     async fn write(&mut self, message: &BeMessage<'_>) -> io::Result<()> {
         self.write_no_flush(message)?;
         self.flush().await?;
@@ -978,6 +1787,62 @@ where
     }
 }
 
+/// The ALPN protocol ID Postgres direct-SSL connections negotiate, in `Ssl::set_alpn_protos`'s
+/// wire format (a length byte followed by the protocol name).
+const ALPN_POSTGRESQL_WIRE: &[u8] = b"\x0apostgresql";
+/// The same protocol ID as returned by `SslRef::selected_alpn_protocol`, i.e. without the
+/// length-prefix `set_alpn_protos` itself expects.
+const ALPN_POSTGRESQL_PROTOCOL: &[u8] = b"postgresql";
+
+/// Wraps a freshly accepted stream whose first byte was already consumed by a caller peeking
+/// for a direct-TLS `ClientHello` (see [`PgProtocol::new_direct_tls`]), replaying that byte to
+/// the first poll_read so no bytes are lost to whatever reads from the stream next.
+pub struct Prefixed<S> {
+    first_byte: Option<u8>,
+    inner: S,
+}
+
+impl<S> Prefixed<S> {
+    fn new(inner: S) -> Self {
+        Self {
+            first_byte: None,
+            inner,
+        }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for Prefixed<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if let Some(b) = self.first_byte.take() {
+            buf.put_slice(&[b]);
+            return Poll::Ready(Ok(()));
+        }
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for Prefixed<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
 /// The logic of Conn is very simple, just a static dispatcher for TcpStream: Unencrypted or Ssl:
 /// Encrypted.
 pub enum Conn<S> {
@@ -1005,6 +1870,7 @@ where
         Ok(PgStream {
             stream: Some(stream),
             write_buf: BytesMut::with_capacity(10 * 1024),
+            client_encoding: self.client_encoding,
         })
     }
 }
@@ -1034,6 +1900,30 @@ where
         }
     }
 
+    fn write_changed_parameter_status_msg_no_flush(
+        &mut self,
+        status: &ParameterStatus,
+    ) -> io::Result<()> {
+        match self {
+            Conn::Unencrypted(s) => s.write_changed_parameter_status_msg_no_flush(status),
+            Conn::Ssl(s) => s.write_changed_parameter_status_msg_no_flush(status),
+        }
+    }
+
+    fn decode_client_text(&self, bytes: &[u8]) -> Bytes {
+        match self {
+            Conn::Unencrypted(s) => s.decode_client_text(bytes),
+            Conn::Ssl(s) => s.decode_client_text(bytes),
+        }
+    }
+
+    fn client_encoding(&self) -> &'static Encoding {
+        match self {
+            Conn::Unencrypted(s) => s.client_encoding(),
+            Conn::Ssl(s) => s.client_encoding(),
+        }
+    }
+
     pub fn write_no_flush(&mut self, message: &BeMessage<'_>) -> io::Result<()> {
         match self {
             Conn::Unencrypted(s) => s.write_no_flush(message),
@@ -1063,6 +1953,54 @@ where
             Conn::Ssl(_s) => panic!("can not turn a ssl stream into a ssl stream"),
         }
     }
+
+    fn write_buf_len(&self) -> usize {
+        match self {
+            Conn::Unencrypted(s) => s.write_buf_len(),
+            Conn::Ssl(s) => s.write_buf_len(),
+        }
+    }
+
+    /// Starts a [`DataRowBatch`] accumulating into this connection's write buffer.
+    fn data_row_batch(&mut self) -> DataRowBatch<'_, S> {
+        DataRowBatch::new(self)
+    }
+}
+
+/// Accumulates outgoing `DataRow` messages into the connection's write buffer and flushes once
+/// the buffer grows past [`Self::FLUSH_THRESHOLD`], so streaming a large result set pays for one
+/// write per `FLUSH_THRESHOLD` bytes of rows instead of once per row while still bounding how
+/// much is held in memory at a time.
+struct DataRowBatch<'a, S> {
+    conn: &'a mut Conn<S>,
+}
+
+impl<'a, S> DataRowBatch<'a, S>
+where
+    S: AsyncWrite + AsyncRead + Unpin,
+{
+    /// Flush once the buffered-but-unsent bytes reach this size.
+    const FLUSH_THRESHOLD: usize = 8 * 1024;
+
+    fn new(conn: &'a mut Conn<S>) -> Self {
+        Self { conn }
+    }
+
+    /// Appends `message` (a `BeMessage::DataRow`) to the batch, flushing once the buffer has
+    /// grown past [`Self::FLUSH_THRESHOLD`].
+    async fn write_row(&mut self, message: &BeMessage<'_>) -> io::Result<()> {
+        self.conn.write_no_flush(message)?;
+        if self.conn.write_buf_len() >= Self::FLUSH_THRESHOLD {
+            self.conn.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Flushes whatever rows are still buffered below the threshold. Must be called once the
+    /// batch is done, or trailing rows are left unsent until some later, unrelated flush.
+    async fn finish(self) -> io::Result<()> {
+        self.conn.flush().await
+    }
 }
 
 fn build_ssl_ctx_from_config(tls_config: &TlsConfig) -> PsqlResult<SslContext> {
@@ -1082,11 +2020,60 @@ fn build_ssl_ctx_from_config(tls_config: &TlsConfig) -> PsqlResult<SslContext> {
     acceptor
         .set_certificate_chain_file(cert_path)
         .map_err(|e| PsqlError::Uncategorized(e.into()))?;
+
+    if tls_config.verify_mode != ClientCertVerifyMode::Disabled {
+        let ca_path = tls_config.ca.as_ref().ok_or_else(|| {
+            PsqlError::Uncategorized("client cert verification requires `ca` to be set".into())
+        })?;
+        acceptor
+            .set_ca_file(ca_path)
+            .map_err(|e| PsqlError::Uncategorized(e.into()))?;
+        let mut verify = openssl::ssl::SslVerifyMode::PEER;
+        if tls_config.verify_mode == ClientCertVerifyMode::VerifyFull {
+            verify |= openssl::ssl::SslVerifyMode::FAIL_IF_NO_PEER_CERT;
+        }
+        acceptor.set_verify(verify);
+    }
+
+    // Advertise and require the `postgresql` ALPN protocol. Classic `SSLRequest` clients don't
+    // send ALPN at all and are unaffected; direct-TLS clients (`PgProtocol::new_direct_tls`) are
+    // required to negotiate it, so a TLS connection that isn't actually speaking our wire
+    // protocol can't be mistaken for one that is.
+    acceptor
+        .set_alpn_protos(ALPN_POSTGRESQL_WIRE)
+        .map_err(|e| PsqlError::Uncategorized(e.into()))?;
+    acceptor.set_alpn_select_callback(|_ssl, client_protos| {
+        openssl::ssl::select_next_proto(ALPN_POSTGRESQL_WIRE, client_protos)
+            .ok_or(AlpnError::NOACK)
+    });
+
     let acceptor = acceptor.build();
 
     Ok(acceptor.into_context())
 }
 
+/// Extracts the peer certificate's subject, preferring a SAN (`subjectAltName`) DNS/email entry
+/// over the certificate's CN, matching how most TLS libraries resolve a certificate's identity
+/// for `clientcert=verify-full`-style comparisons.
+fn peer_cert_subject<S>(ssl_stream: &SslStream<S>) -> Option<String> {
+    let cert = ssl_stream.ssl().peer_certificate()?;
+    if let Some(names) = cert.subject_alt_names() {
+        for name in names {
+            if let Some(dns) = name.dnsname() {
+                return Some(dns.to_string());
+            }
+            if let Some(email) = name.email() {
+                return Some(email.to_string());
+            }
+        }
+    }
+    cert.subject_name()
+        .entries_by_nid(openssl::nid::Nid::COMMONNAME)
+        .next()
+        .and_then(|entry| entry.data().as_utf8().ok())
+        .map(|s| s.to_string())
+}
+
 pub mod truncated_fmt {
     use std::fmt::*;
 